@@ -12,6 +12,16 @@ impl BondingCurve {
         current_supply: u64,
         amount: u64,
     ) -> Result<u64> {
+        Ok(Self::calculate_buy_quote(params, current_supply, amount)?.0)
+    }
+
+    /// Calculate the cost to buy a specific amount of tokens, returning
+    /// (total cost including fee, fee)
+    pub fn calculate_buy_quote(
+        params: &BondingCurveParams,
+        current_supply: u64,
+        amount: u64,
+    ) -> Result<(u64, u64)> {
         require!(amount > 0, PredictionPumpError::InvalidPrice);
         require!(current_supply + amount <= params.max_supply, PredictionPumpError::InvalidMaxSupply);
 
@@ -25,13 +35,11 @@ impl BondingCurve {
             .ok_or(PredictionPumpError::MathOverflow)?;
 
         // Add trading fee
-        let fee = total_cost.checked_mul(params.fee_rate as u64)
-            .ok_or(PredictionPumpError::MathOverflow)?
-            .checked_div(10000)
-            .ok_or(PredictionPumpError::MathOverflow)?;
+        let fee = Self::calculate_fee(params, total_cost)?;
 
-        total_cost.checked_add(fee)
-            .ok_or(PredictionPumpError::MathOverflow.into())
+        let total_cost = total_cost.checked_add(fee)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        Ok((total_cost, fee))
     }
 
     /// Calculate the payout for selling a specific amount of tokens
@@ -41,6 +49,16 @@ impl BondingCurve {
         current_supply: u64,
         amount: u64,
     ) -> Result<u64> {
+        Ok(Self::calculate_sell_quote(params, current_supply, amount)?.0)
+    }
+
+    /// Calculate the payout for selling a specific amount of tokens, returning
+    /// (payout net of fee, fee)
+    pub fn calculate_sell_quote(
+        params: &BondingCurveParams,
+        current_supply: u64,
+        amount: u64,
+    ) -> Result<(u64, u64)> {
         require!(amount > 0, PredictionPumpError::InvalidPrice);
         require!(amount <= current_supply, PredictionPumpError::InvalidMaxSupply);
 
@@ -54,12 +72,18 @@ impl BondingCurve {
             .ok_or(PredictionPumpError::MathOverflow)?;
 
         // Subtract trading fee
-        let fee = total_payout.checked_mul(params.fee_rate as u64)
-            .ok_or(PredictionPumpError::MathOverflow)?
-            .checked_div(10000)
+        let fee = Self::calculate_fee(params, total_payout)?;
+
+        let total_payout = total_payout.checked_sub(fee)
             .ok_or(PredictionPumpError::MathOverflow)?;
+        Ok((total_payout, fee))
+    }
 
-        total_payout.checked_sub(fee)
+    /// Trading fee charged on a trade of the given value
+    pub fn calculate_fee(params: &BondingCurveParams, value: u64) -> Result<u64> {
+        value.checked_mul(params.fee_rate as u64)
+            .ok_or(PredictionPumpError::MathOverflow)?
+            .checked_div(10000)
             .ok_or(PredictionPumpError::MathOverflow.into())
     }
 
@@ -356,4 +380,19 @@ mod tests {
         assert!(loss_percentage >= expected_loss / 2);
         assert!(loss_percentage <= expected_loss * 3);
     }
+
+    #[test]
+    fn test_quotes_split_out_fee() {
+        let params = create_test_params();
+        let current_supply = 5000;
+        let amount = 100;
+
+        let (cost, buy_fee) = BondingCurve::calculate_buy_quote(&params, current_supply, amount).unwrap();
+        assert_eq!(cost, BondingCurve::calculate_buy_price(&params, current_supply, amount).unwrap());
+        assert_eq!(buy_fee, BondingCurve::calculate_fee(&params, cost - buy_fee).unwrap());
+
+        let (payout, sell_fee) = BondingCurve::calculate_sell_quote(&params, current_supply, amount).unwrap();
+        assert_eq!(payout, BondingCurve::calculate_sell_price(&params, current_supply, amount).unwrap());
+        assert_eq!(sell_fee, BondingCurve::calculate_fee(&params, payout + sell_fee).unwrap());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::{Market, PredictionPumpError};
+
+/// Accounts for moving a market's collateral. SOL markets leave every field out
+/// and hold collateral as vault lamports; SPL markets pass the collateral mint,
+/// the vault's associated token account and the trader's token account
+#[derive(Accounts)]
+pub struct Collateral<'info> {
+    pub collateral_mint: Option<Account<'info, Mint>>,
+
+    /// Associated token account of the market vault PDA for the collateral mint
+    #[account(mut)]
+    pub vault_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Collateral token account of whoever pays into or is paid from the vault
+    #[account(mut)]
+    pub party_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+impl<'info> Collateral<'info> {
+    /// Vault token account and mint of an SPL market, checked against the market;
+    /// `None` for a SOL market
+    fn spl_vault(
+        &self,
+        market: &Market,
+        market_vault: &Pubkey,
+    ) -> Result<Option<(&Account<'info, TokenAccount>, &Account<'info, Mint>)>> {
+        let Some(collateral_mint) = market.collateral_mint else {
+            return Ok(None);
+        };
+
+        let mint = self
+            .collateral_mint
+            .as_ref()
+            .filter(|mint| mint.key() == collateral_mint)
+            .ok_or(PredictionPumpError::InvalidCollateralAccount)?;
+        let vault = self
+            .vault_token_account
+            .as_ref()
+            .filter(|vault| vault.key() == get_associated_token_address(market_vault, &collateral_mint))
+            .ok_or(PredictionPumpError::InvalidCollateralAccount)?;
+        Ok(Some((vault, mint)))
+    }
+
+    /// Collateral token account of `owner`, checked against the vault's mint
+    fn party_account(&self, mint: &Pubkey, owner: &Pubkey) -> Result<&Account<'info, TokenAccount>> {
+        self.party_token_account
+            .as_ref()
+            .filter(|account| account.mint == *mint && account.owner == *owner)
+            .ok_or(PredictionPumpError::InvalidCollateralAccount.into())
+    }
+
+    fn token_program(&self) -> Result<AccountInfo<'info>> {
+        self.token_program
+            .as_ref()
+            .map(|program| program.to_account_info())
+            .ok_or(PredictionPumpError::InvalidCollateralAccount.into())
+    }
+
+    /// Collateral in the vault backing outcome tokens. SOL vaults also hold
+    /// their rent reserve and the creator bond, which are left out
+    pub fn balance(&self, market: &Market, market_vault: &AccountInfo<'info>) -> Result<u64> {
+        let available = match self.spl_vault(market, market_vault.key)? {
+            Some((vault, _)) => vault.amount,
+            None => {
+                let rent_exempt_minimum = Rent::get()?.minimum_balance(market_vault.data_len());
+                market_vault
+                    .lamports()
+                    .saturating_sub(rent_exempt_minimum)
+                    .saturating_sub(market.creator_bond)
+            }
+        };
+        Ok(market.collateral_in(available))
+    }
+
+    /// Move `amount` of collateral from a signer into the vault
+    pub fn deposit(
+        &self,
+        market: &Market,
+        market_vault: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        match self.spl_vault(market, market_vault.key)? {
+            Some((vault, mint)) => transfer_checked(
+                CpiContext::new(
+                    self.token_program()?,
+                    TransferChecked {
+                        from: self.party_account(&mint.key(), payer.key)?.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: vault.to_account_info(),
+                        authority: payer.clone(),
+                    },
+                ),
+                amount,
+                mint.decimals,
+            ),
+            None => transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: payer.clone(),
+                        to: market_vault.clone(),
+                    },
+                ),
+                amount,
+            ),
+        }
+    }
+
+    /// Pay `amount` of collateral out of the vault, signing as the vault PDA
+    pub fn withdraw(
+        &self,
+        market: &Account<'info, Market>,
+        market_vault: &AccountInfo<'info>,
+        vault_bump: u8,
+        recipient: &AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        let Some((vault, mint)) = self.spl_vault(market, market_vault.key)? else {
+            **market_vault.try_borrow_mut_lamports()? = market_vault
+                .lamports()
+                .checked_sub(amount)
+                .ok_or(PredictionPumpError::InsufficientVaultFunds)?;
+
+            **recipient.try_borrow_mut_lamports()? = recipient
+                .lamports()
+                .checked_add(amount)
+                .ok_or(PredictionPumpError::MathOverflow)?;
+            return Ok(());
+        };
+
+        let market_key = market.key();
+        let vault_seeds: &[&[u8]] = &[b"market_vault", market_key.as_ref(), &[vault_bump]];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program()?,
+                TransferChecked {
+                    from: vault.to_account_info(),
+                    mint: mint.to_account_info(),
+                    to: self.party_account(&mint.key(), recipient.key)?.to_account_info(),
+                    authority: market_vault.clone(),
+                },
+                &[vault_seeds],
+            ),
+            amount,
+            mint.decimals,
+        )
+    }
+}
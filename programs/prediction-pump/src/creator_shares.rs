@@ -0,0 +1,400 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{
+    mint_to, set_authority, spl_token::instruction::AuthorityType, transfer, Mint, MintTo,
+    SetAuthority, Token, TokenAccount, Transfer,
+};
+
+use crate::{
+    CreatorStake, Market, MarketVault, PauseFlag, PredictionPumpError, ProtocolConfig,
+    CREATOR_SHARE_SUPPLY,
+};
+use crate::collateral::{
+    Collateral, CollateralBumps, __client_accounts_collateral, __cpi_client_accounts_collateral,
+};
+
+/// Mint the market's fixed supply of creator shares to the creator. Shares are
+/// ordinary SPL tokens, so the creator fee stream can be sold or split
+pub(crate) fn initialize_creator_shares(ctx: Context<InitializeCreatorShares>) -> Result<()> {
+    let market_key = ctx.accounts.market.key();
+    let vault_seeds: &[&[u8]] = &[
+        b"market_vault",
+        market_key.as_ref(),
+        &[ctx.accounts.market_vault.bump],
+    ];
+
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.creator_mint.to_account_info(),
+                to: ctx.accounts.creator_token_account.to_account_info(),
+                authority: ctx.accounts.market_vault.to_account_info(),
+            },
+            &[vault_seeds],
+        ),
+        CREATOR_SHARE_SUPPLY,
+    )?;
+
+    // Fix the supply for good
+    set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            SetAuthority {
+                current_authority: ctx.accounts.market_vault.to_account_info(),
+                account_or_mint: ctx.accounts.creator_mint.to_account_info(),
+            },
+            &[vault_seeds],
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    Ok(())
+}
+
+/// Stake creator shares to start earning the creator share of trading fees
+pub(crate) fn stake_creator_shares(ctx: Context<StakeCreatorShares>, amount: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let stake = &mut ctx.accounts.stake;
+    require!(amount > 0, PredictionPumpError::InvalidStakeAmount);
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.holder_token_account.to_account_info(),
+                to: ctx.accounts.stake_vault.to_account_info(),
+                authority: ctx.accounts.holder.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    if stake.owner == Pubkey::default() {
+        stake.market = market.key();
+        stake.owner = ctx.accounts.holder.key();
+        stake.bump = ctx.bumps.stake;
+    }
+    stake.deposit(amount, market.creator_rewards.reward_per_token)?;
+    market.creator_rewards.stake(amount)?;
+
+    Ok(())
+}
+
+/// Return staked creator shares to their owner; accrued fees stay claimable
+pub(crate) fn unstake_creator_shares(ctx: Context<UnstakeCreatorShares>, amount: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let stake = &mut ctx.accounts.stake;
+    require!(amount > 0, PredictionPumpError::InvalidStakeAmount);
+
+    stake.withdraw(amount, market.creator_rewards.reward_per_token)?;
+    market.creator_rewards.unstake(amount)?;
+
+    let market_key = market.key();
+    let vault_seeds: &[&[u8]] = &[
+        b"market_vault",
+        market_key.as_ref(),
+        &[ctx.accounts.market_vault.bump],
+    ];
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.stake_vault.to_account_info(),
+                to: ctx.accounts.holder_token_account.to_account_info(),
+                authority: ctx.accounts.market_vault.to_account_info(),
+            },
+            &[vault_seeds],
+        ),
+        amount,
+    )?;
+
+    Ok(())
+}
+
+/// Pay a staker's pro-rata share of the creator fees accrued while staked
+pub(crate) fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let stake = &mut ctx.accounts.stake;
+
+    let amount = stake.claim(market.creator_rewards.reward_per_token)?;
+    market.fees.pay_creator(amount)?;
+
+    ctx.accounts.collateral.withdraw(
+        market,
+        &ctx.accounts.market_vault.to_account_info(),
+        ctx.accounts.market_vault.bump,
+        &ctx.accounts.holder.to_account_info(),
+        amount,
+    )?;
+
+    emit!(CreatorFeesClaimedEvent {
+        market: market.key(),
+        holder: ctx.accounts.holder.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+/// Pay the market creator the creator fees that arrived while no shares were
+/// staked, including every creator fee of a market that never minted shares
+pub(crate) fn claim_unshared_creator_fees(ctx: Context<ClaimUnsharedCreatorFees>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    let amount = market.creator_rewards.take_undistributed()?;
+    market.fees.pay_creator(amount)?;
+
+    ctx.accounts.collateral.withdraw(
+        market,
+        &ctx.accounts.market_vault.to_account_info(),
+        ctx.accounts.market_vault.bump,
+        &ctx.accounts.creator.to_account_info(),
+        amount,
+    )?;
+
+    emit!(CreatorFeesClaimedEvent {
+        market: market.key(),
+        holder: ctx.accounts.creator.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeCreatorShares<'info> {
+    #[account(
+        has_one = creator @ PredictionPumpError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market_vault.bump
+    )]
+    pub market_vault: Account<'info, MarketVault>,
+
+    /// One creator share mint per market
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"creator_mint", market.key().as_ref()],
+        bump,
+        mint::decimals = 6,
+        mint::authority = market_vault
+    )]
+    pub creator_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = creator_mint,
+        associated_token::authority = creator
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    /// Holds staked creator shares
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"creator_stake_vault", market.key().as_ref()],
+        bump,
+        token::mint = creator_mint,
+        token::authority = market_vault
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(InitializeCreatorShares::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct StakeCreatorShares<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"creator_mint", market.key().as_ref()],
+        bump
+    )]
+    pub creator_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_stake_vault", market.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = CreatorStake::LEN,
+        seeds = [b"creator_stake", market.key().as_ref(), holder.key().as_ref()],
+        bump
+    )]
+    pub stake: Account<'info, CreatorStake>,
+
+    #[account(
+        mut,
+        token::mint = creator_mint,
+        token::authority = holder
+    )]
+    pub holder_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(StakeCreatorShares::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnstakeCreatorShares<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market_vault.bump
+    )]
+    pub market_vault: Account<'info, MarketVault>,
+
+    #[account(
+        seeds = [b"creator_mint", market.key().as_ref()],
+        bump
+    )]
+    pub creator_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_stake_vault", market.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_stake", market.key().as_ref(), holder.key().as_ref()],
+        bump = stake.bump,
+        has_one = market,
+        constraint = stake.owner == holder.key() @ PredictionPumpError::Unauthorized
+    )]
+    pub stake: Account<'info, CreatorStake>,
+
+    #[account(
+        mut,
+        token::mint = creator_mint
+    )]
+    pub holder_token_account: Account<'info, TokenAccount>,
+
+    pub holder: Signer<'info>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(UnstakeCreatorShares::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCreatorFees<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market_vault.bump
+    )]
+    pub market_vault: Account<'info, MarketVault>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_stake", market.key().as_ref(), holder.key().as_ref()],
+        bump = stake.bump,
+        has_one = market,
+        constraint = stake.owner == holder.key() @ PredictionPumpError::Unauthorized
+    )]
+    pub stake: Account<'info, CreatorStake>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    /// Collateral mint and token accounts; omitted for SOL markets
+    pub collateral: Collateral<'info>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(ClaimCreatorFees::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimUnsharedCreatorFees<'info> {
+    #[account(
+        mut,
+        has_one = creator @ PredictionPumpError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market_vault.bump
+    )]
+    pub market_vault: Account<'info, MarketVault>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Collateral mint and token accounts; omitted for SOL markets
+    pub collateral: Collateral<'info>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(ClaimUnsharedCreatorFees::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+// Events for off-chain indexing and real-time updates
+
+#[event]
+pub struct CreatorFeesClaimedEvent {
+    pub market: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+}
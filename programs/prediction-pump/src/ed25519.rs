@@ -0,0 +1,196 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{ed25519_program, instruction::Instruction};
+
+use crate::PredictionPumpError;
+
+// Layout of the Ed25519 precompile instruction data
+const PUBKEY_SERIALIZED_SIZE: usize = 32;
+const SIGNATURE_SERIALIZED_SIZE: usize = 64;
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+
+/// Offsets entry describing where the precompile finds one signature
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ed25519SignatureOffsets {
+    pub signature_offset: u16,
+    pub signature_instruction_index: u16,
+    pub public_key_offset: u16,
+    pub public_key_instruction_index: u16,
+    pub message_data_offset: u16,
+    pub message_data_size: u16,
+    pub message_instruction_index: u16,
+}
+
+impl Ed25519SignatureOffsets {
+    fn unpack(data: &[u8]) -> Self {
+        let read = |i: usize| u16::from_le_bytes([data[i * 2], data[i * 2 + 1]]);
+        Ed25519SignatureOffsets {
+            signature_offset: read(0),
+            signature_instruction_index: read(1),
+            public_key_offset: read(2),
+            public_key_instruction_index: read(3),
+            message_data_offset: read(4),
+            message_data_size: read(5),
+            message_instruction_index: read(6),
+        }
+    }
+}
+
+/// Check that `ix` is an Ed25519 precompile instruction verifying exactly one
+/// signature by `signer` over `message`.
+///
+/// The precompile itself rejects the transaction if the signature is invalid, so
+/// a matching instruction in the same transaction proves `signer` signed `message`.
+/// All data must be inline in the precompile instruction (index `u16::MAX`) so it
+/// cannot point at bytes elsewhere in the transaction.
+pub fn verify_ed25519_instruction(ix: &Instruction, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    require!(
+        ix.program_id == ed25519_program::ID,
+        PredictionPumpError::InvalidSignatureInstruction
+    );
+    require!(ix.accounts.is_empty(), PredictionPumpError::InvalidSignatureInstruction);
+
+    let data = &ix.data;
+    require!(
+        data.len() >= SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SERIALIZED_SIZE,
+        PredictionPumpError::InvalidSignatureInstruction
+    );
+    require!(data[0] == 1, PredictionPumpError::InvalidSignatureInstruction);
+
+    let offsets = Ed25519SignatureOffsets::unpack(
+        &data[SIGNATURE_OFFSETS_START..SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SERIALIZED_SIZE],
+    );
+    require!(
+        offsets.signature_instruction_index == u16::MAX
+            && offsets.public_key_instruction_index == u16::MAX
+            && offsets.message_instruction_index == u16::MAX,
+        PredictionPumpError::InvalidSignatureInstruction
+    );
+
+    let signature_end = offsets.signature_offset as usize + SIGNATURE_SERIALIZED_SIZE;
+    require!(signature_end <= data.len(), PredictionPumpError::InvalidSignatureInstruction);
+
+    let public_key = read_slice(data, offsets.public_key_offset, PUBKEY_SERIALIZED_SIZE)?;
+    require!(public_key == signer.as_ref(), PredictionPumpError::SignatureMismatch);
+
+    let signed_message = read_slice(data, offsets.message_data_offset, offsets.message_data_size as usize)?;
+    require!(signed_message == message, PredictionPumpError::SignatureMismatch);
+
+    Ok(())
+}
+
+fn read_slice(data: &[u8], offset: u16, len: usize) -> Result<&[u8]> {
+    let start = offset as usize;
+    let end = start
+        .checked_add(len)
+        .ok_or(PredictionPumpError::InvalidSignatureInstruction)?;
+    data.get(start..end)
+        .ok_or(PredictionPumpError::InvalidSignatureInstruction.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OracleData;
+
+    /// Build precompile instruction data in the layout produced by
+    /// `new_ed25519_instruction` (signature bytes are not checked here)
+    fn ed25519_instruction(signer: &Pubkey, message: &[u8]) -> Instruction {
+        let public_key_offset = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+        let signature_offset = public_key_offset + PUBKEY_SERIALIZED_SIZE;
+        let message_data_offset = signature_offset + SIGNATURE_SERIALIZED_SIZE;
+
+        let mut data = vec![1u8, 0u8];
+        for value in [
+            signature_offset as u16,
+            u16::MAX,
+            public_key_offset as u16,
+            u16::MAX,
+            message_data_offset as u16,
+            message.len() as u16,
+            u16::MAX,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[7u8; SIGNATURE_SERIALIZED_SIZE]);
+        data.extend_from_slice(message);
+
+        Instruction {
+            program_id: ed25519_program::ID,
+            accounts: vec![],
+            data,
+        }
+    }
+
+    fn report_message() -> Vec<u8> {
+        OracleData::report_message(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            1,
+            9500,
+            1691234567,
+            &[42u8; 32],
+        )
+    }
+
+    #[test]
+    fn test_verify_matching_signature_instruction() {
+        let signer = Pubkey::new_unique();
+        let message = report_message();
+        let ix = ed25519_instruction(&signer, &message);
+
+        assert!(verify_ed25519_instruction(&ix, &signer, &message).is_ok());
+    }
+
+    #[test]
+    fn test_verify_rejects_other_signer_or_message() {
+        let signer = Pubkey::new_unique();
+        let message = report_message();
+        let ix = ed25519_instruction(&signer, &message);
+
+        assert!(verify_ed25519_instruction(&ix, &Pubkey::new_unique(), &message).is_err());
+        assert!(verify_ed25519_instruction(&ix, &signer, &report_message()).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_program() {
+        let signer = Pubkey::new_unique();
+        let message = report_message();
+        let mut ix = ed25519_instruction(&signer, &message);
+        ix.program_id = Pubkey::new_unique();
+
+        assert!(verify_ed25519_instruction(&ix, &signer, &message).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_data_in_other_instructions() {
+        let signer = Pubkey::new_unique();
+        let message = report_message();
+        let mut ix = ed25519_instruction(&signer, &message);
+        // Point the public key at instruction 0 instead of the precompile itself
+        ix.data[8..10].copy_from_slice(&0u16.to_le_bytes());
+
+        assert!(verify_ed25519_instruction(&ix, &signer, &message).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_multiple_signatures() {
+        let signer = Pubkey::new_unique();
+        let message = report_message();
+        let mut ix = ed25519_instruction(&signer, &message);
+        ix.data[0] = 2;
+
+        assert!(verify_ed25519_instruction(&ix, &signer, &message).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_truncated_data() {
+        let signer = Pubkey::new_unique();
+        let message = report_message();
+        let mut ix = ed25519_instruction(&signer, &message);
+        ix.data.truncate(ix.data.len() - 1);
+
+        assert!(verify_ed25519_instruction(&ix, &signer, &message).is_err());
+    }
+}
@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{self, AssociatedToken};
 use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{self, TokenInterface};
 use anchor_lang::solana_program;
+use anchor_lang::system_program::{transfer, Transfer};
 
 pub mod settlement;
 pub use settlement::*;
@@ -8,6 +11,40 @@ pub use settlement::*;
 pub mod bonding_curve;
 pub use bonding_curve::*;
 
+pub mod switchboard;
+pub use switchboard::*;
+
+pub mod optimistic;
+pub use optimistic::*;
+
+pub mod ed25519;
+pub use ed25519::*;
+
+pub mod signed_report;
+pub use signed_report::*;
+
+pub mod trading;
+pub use trading::*;
+
+pub mod pause;
+pub use pause::*;
+
+pub mod liquidity;
+pub use liquidity::*;
+
+pub mod creator_shares;
+pub use creator_shares::*;
+
+pub mod collateral;
+pub use collateral::{Collateral, CollateralBumps};
+use collateral::{__client_accounts_collateral, __cpi_client_accounts_collateral};
+
+pub mod outcome_mint;
+pub use outcome_mint::*;
+
+pub mod metaplex;
+pub use metaplex::*;
+
 #[cfg(test)]
 pub mod tests;
 
@@ -17,14 +54,111 @@ declare_id!("2vi9hVuYBws8GwFqPG6eRQRFoEMGfkCny2Lbvf3pFuzu");
 pub const MINIMUM_LIQUIDITY_THRESHOLD: u64 = 1_000_000; // 0.001 SOL in lamports
 pub const MINIMUM_TRADING_VOLUME: u64 = 10_000_000; // 0.01 SOL in lamports
 
+// Oracle reliability adjustments applied when disputes are resolved (score is 0-10000)
+pub const RELIABILITY_PENALTY: u16 = 1000; // outcome overridden by voters
+pub const RELIABILITY_REWARD: u16 = 250; // outcome upheld by voters
+pub const MIN_RELIABILITY_SCORE: u16 = 5000; // providers below this are deactivated
+
+// Vote cast to keep the original oracle outcome
+pub const UPHOLD_ORIGINAL_VOTE: u8 = 255;
+
+// Vote cast to void a malformed market; holders of either outcome are refunded
+pub const VOID_MARKET_VOTE: u8 = 254;
+
+// How long after settlement its outcome can still be disputed
+pub const DISPUTE_WINDOW: i64 = 7 * 24 * 60 * 60; // 7 days
+
+// How long after settlement the creator bond stays slashable by a void ruling
+pub const CREATOR_BOND_LOCKUP: i64 = DISPUTE_WINDOW;
+
+// Space reserved for a dispute reason
+pub const MAX_DISPUTE_REASON_LEN: usize = 200;
+
+// Upper bound on appeal rounds after the first vote (bounds Dispute account space)
+pub const MAX_DISPUTE_APPEALS: u8 = 3;
+
+// Round seed of the disputer's reward receipt, outside the range of voting rounds
+pub const DISPUTER_RECEIPT_ROUND: u8 = u8::MAX;
+
+// Evidence records a dispute accepts, and space reserved for each record's URI
+pub const MAX_DISPUTE_EVIDENCE: u16 = 32;
+pub const MAX_EVIDENCE_URI_LEN: usize = 200;
+
+// Fixed-point scale of the LP fee-per-share and creator reward-per-token accumulators
+pub const FEE_PER_SHARE_PRECISION: u128 = 1_000_000_000_000;
+
+// How long liquidity stays in the pool after each deposit, so fees cannot be
+// captured by depositing just before a trade and withdrawing right after
+pub const LP_LOCKUP: i64 = 3 * 24 * 60 * 60; // 3 days
+
+// Decimals of native SOL collateral (lamports)
+pub const NATIVE_SOL_DECIMALS: u8 = 9;
+
+// Creator shares minted per market (1,000,000 tokens at 6 decimals)
+pub const CREATOR_SHARE_SUPPLY: u64 = 1_000_000_000_000;
+
+// Share of a losing voter's locked stake redistributed to winning voters
+pub const VOTER_SLASH_BPS: u16 = 1000; // 10%
+
+// Maximum raw evidence attached to an oracle report (e.g. a URL hash or price value)
+pub const MAX_ORACLE_EVIDENCE_LEN: usize = 64;
+
 #[program]
 pub mod prediction_pump {
     use super::*;
 
-    pub fn initialize(_ctx: Context<Initialize>) -> Result<()> {
+    /// Create the global protocol configuration; only the program's upgrade
+    /// authority can call it, and becomes the admin
+    pub fn initialize(ctx: Context<Initialize>, params: ProtocolConfigParams) -> Result<()> {
+        let protocol_config = &mut ctx.accounts.protocol_config;
+
+        **protocol_config = ProtocolConfig::new(
+            ctx.accounts.admin.key(),
+            params,
+            ctx.bumps.protocol_config,
+        )?;
+
+        Ok(())
+    }
+
+    /// Update protocol parameters; existing markets keep the fee split they were created with
+    pub fn update_config(ctx: Context<UpdateConfig>, params: ProtocolConfigParams) -> Result<()> {
+        let protocol_config = &mut ctx.accounts.protocol_config;
+
+        let pending_admin = protocol_config.pending_admin;
+        let paused = protocol_config.paused;
+        **protocol_config = ProtocolConfig::new(
+            protocol_config.admin,
+            params,
+            protocol_config.bump,
+        )?;
+        protocol_config.pending_admin = pending_admin;
+        protocol_config.paused = paused;
+
+        Ok(())
+    }
+
+    /// Nominate a new admin; the transfer completes when they call `accept_admin`
+    pub fn transfer_admin(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.protocol_config.pending_admin = Some(new_admin);
         Ok(())
     }
 
+    /// Accept a pending admin nomination
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        ctx.accounts.protocol_config.accept_admin(ctx.accounts.new_admin.key())
+    }
+
+    /// Pause or resume instruction groups protocol-wide (guardian or admin)
+    pub fn set_protocol_pause(ctx: Context<SetProtocolPause>, paused: u8) -> Result<()> {
+        pause::set_protocol_pause(ctx, paused)
+    }
+
+    /// Pause or resume instruction groups for one market (guardian or admin)
+    pub fn set_market_pause(ctx: Context<SetMarketPause>, paused: u8) -> Result<()> {
+        pause::set_market_pause(ctx, paused)
+    }
+
     /// Create a new prediction market with bonding curve pricing
     pub fn create_market(
         ctx: Context<CreateMarket>,
@@ -36,7 +170,6 @@ pub mod prediction_pump {
         max_supply: u64,
         fee_rate: u16,
     ) -> Result<()> {
-        let market = &mut ctx.accounts.market;
         let creator = ctx.accounts.creator.key();
         let oracle_source = ctx.accounts.oracle_source.key();
 
@@ -44,11 +177,13 @@ pub mod prediction_pump {
         require!(outcome_count == 2, PredictionPumpError::InsufficientOutcomes);
 
         // Create bonding curve parameters
+        let protocol_config = &ctx.accounts.protocol_config;
         let bonding_curve_params = BondingCurveParams::new(
             initial_price,
             curve_steepness,
             max_supply,
             fee_rate,
+            &protocol_config.fee_bounds,
         )?;
 
         // Collect outcome token mints (binary market)
@@ -57,7 +192,12 @@ pub mod prediction_pump {
             ctx.accounts.outcome_mint_1.key(),
         ];
 
+        for outcome in 0..outcome_count {
+            ctx.accounts.create_outcome_mint(outcome, &description, ctx.bumps.market_vault)?;
+        }
+
         // Initialize market
+        let market = &mut ctx.accounts.market;
         **market = Market::new(
             creator,
             description,
@@ -65,21 +205,297 @@ pub mod prediction_pump {
             oracle_source,
             outcome_tokens,
             bonding_curve_params,
+            protocol_config,
         )?;
 
+        // SPL markets hold collateral in the vault PDA's associated token account
+        if let Some(collateral_mint) = &ctx.accounts.collateral_mint {
+            let (Some(vault_token_account), Some(associated_token_program)) = (
+                &ctx.accounts.vault_token_account,
+                &ctx.accounts.associated_token_program,
+            ) else {
+                return err!(PredictionPumpError::InvalidCollateralAccount);
+            };
+
+            associated_token::create(CpiContext::new(
+                associated_token_program.to_account_info(),
+                associated_token::Create {
+                    payer: ctx.accounts.creator.to_account_info(),
+                    associated_token: vault_token_account.to_account_info(),
+                    authority: ctx.accounts.market_vault.to_account_info(),
+                    mint: collateral_mint.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+            ))?;
+
+            market.collateral_mint = Some(collateral_mint.key());
+            market.collateral_decimals = collateral_mint.decimals;
+        }
+
+        // Creation fee goes to the protocol treasury
+        if protocol_config.creation_fee > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.creator.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                ),
+                protocol_config.creation_fee,
+            )?;
+        }
+
+        // The creator bond is held in the vault until the market settles
+        if protocol_config.creator_bond > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.creator.to_account_info(),
+                        to: ctx.accounts.market_vault.to_account_info(),
+                    },
+                ),
+                protocol_config.creator_bond,
+            )?;
+        }
+
+        let market_vault = &mut ctx.accounts.market_vault;
+        market_vault.market = market.key();
+        market_vault.bump = ctx.bumps.market_vault;
+
         Ok(())
     }
 
+    /// Buy outcome tokens along the bonding curve
+    pub fn buy_tokens(ctx: Context<BuyTokens>, outcome: u8, amount: u64, max_cost: u64) -> Result<()> {
+        trading::buy_tokens(ctx, outcome, amount, max_cost)
+    }
+
+    /// Sell outcome tokens back to the bonding curve
+    pub fn sell_tokens(ctx: Context<SellTokens>, outcome: u8, amount: u64, min_payout: u64) -> Result<()> {
+        trading::sell_tokens(ctx, outcome, amount, min_payout)
+    }
+
+    /// Deposit collateral into a market's liquidity pool for LP tokens
+    pub fn add_liquidity(ctx: Context<AddLiquidity>, amount: u64, min_shares: u64) -> Result<()> {
+        liquidity::add_liquidity(ctx, amount, min_shares)
+    }
+
+    /// Redeem LP tokens for pool collateral and accrued fees
+    pub fn remove_liquidity(ctx: Context<RemoveLiquidity>, shares: u64, min_amount: u64) -> Result<()> {
+        liquidity::remove_liquidity(ctx, shares, min_amount)
+    }
+
+    /// Create Token Metadata for one of a market's outcome mints
+    pub fn create_outcome_metadata(ctx: Context<CreateOutcomeMetadata>, outcome: u8, uri: String) -> Result<()> {
+        metaplex::create_outcome_metadata(ctx, outcome, uri)
+    }
+
+    /// Mint a market's creator shares to its creator
+    pub fn initialize_creator_shares(ctx: Context<InitializeCreatorShares>) -> Result<()> {
+        creator_shares::initialize_creator_shares(ctx)
+    }
+
+    /// Stake creator shares to earn the creator share of trading fees
+    pub fn stake_creator_shares(ctx: Context<StakeCreatorShares>, amount: u64) -> Result<()> {
+        creator_shares::stake_creator_shares(ctx, amount)
+    }
+
+    /// Withdraw staked creator shares
+    pub fn unstake_creator_shares(ctx: Context<UnstakeCreatorShares>, amount: u64) -> Result<()> {
+        creator_shares::unstake_creator_shares(ctx, amount)
+    }
+
+    /// Withdraw a staker's share of creator fees
+    pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+        creator_shares::claim_creator_fees(ctx)
+    }
+
+    /// Withdraw the creator fees that arrived while no creator shares were staked
+    pub fn claim_unshared_creator_fees(ctx: Context<ClaimUnsharedCreatorFees>) -> Result<()> {
+        creator_shares::claim_unshared_creator_fees(ctx)
+    }
+
+    /// Send the protocol's share of a market's trading fees to the treasury
+    pub fn claim_protocol_fees(ctx: Context<ClaimProtocolFees>) -> Result<()> {
+        trading::claim_protocol_fees(ctx)
+    }
+
     /// Settle a market using oracle data and distribute payouts
     pub fn settle_market(ctx: Context<SettleMarket>) -> Result<()> {
         settlement::settle_market(ctx)
     }
 
+    /// Configure how an oracle price feed result maps to a market outcome; only
+    /// before the market's first trade
+    pub fn set_resolution_condition(
+        ctx: Context<SetResolutionCondition>,
+        comparator: Comparator,
+        threshold: SwitchboardDecimal,
+        max_staleness: i64,
+        min_oracle_results: u32,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.require_untraded()?;
+
+        market.resolution_condition = Some(ResolutionCondition::new(
+            comparator,
+            threshold,
+            max_staleness,
+            min_oracle_results,
+        )?);
+
+        Ok(())
+    }
+
+    /// Settle a market using the latest result of its Switchboard aggregator
+    pub fn settle_market_switchboard(ctx: Context<SettleMarketSwitchboard>) -> Result<()> {
+        switchboard::settle_market_switchboard(ctx)
+    }
+
+    /// Enable optimistic (bonded proposal) resolution for a market; only before
+    /// its first trade
+    pub fn set_optimistic_config(
+        ctx: Context<SetOptimisticConfig>,
+        bond_amount: u64,
+        liveness: i64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.require_untraded()?;
+
+        market.optimistic_config = Some(OptimisticConfig::new(bond_amount, liveness)?);
+
+        Ok(())
+    }
+
+    /// Replace a market's fixed fee rate with a volume-driven one, or pass
+    /// `None` to return to the fixed rate; only before the first trade
+    pub fn set_dynamic_fee(
+        ctx: Context<SetDynamicFee>,
+        params: Option<DynamicFeeParams>,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+
+        market.require_untraded()?;
+
+        market.dynamic_fee = params
+            .map(|params| DynamicFee::new(params, &ctx.accounts.protocol_config.fee_bounds))
+            .transpose()?;
+
+        Ok(())
+    }
+
+    /// Propose the outcome of an optimistic market by posting a bond
+    pub fn propose_outcome(ctx: Context<ProposeOutcome>, proposed_outcome: u8) -> Result<()> {
+        optimistic::propose_outcome(ctx, proposed_outcome)
+    }
+
+    /// Counter-bond a pending proposal and send it to dispute voting
+    pub fn dispute_proposal(ctx: Context<DisputeProposal>, reason: String) -> Result<()> {
+        optimistic::dispute_proposal(ctx, reason)
+    }
+
+    /// Settle an optimistic market and release the proposal bonds
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+        optimistic::finalize_proposal(ctx)
+    }
+
     /// Claim payout for winning tokens
     pub fn claim_payout(ctx: Context<ClaimPayout>) -> Result<()> {
         settlement::claim_payout(ctx)
     }
 
+    /// Refund the creator bond after a clean settlement, or slash it to the
+    /// treasury if the market was voided (anyone may call)
+    pub fn release_creator_bond(ctx: Context<ReleaseCreatorBond>) -> Result<()> {
+        settlement::release_creator_bond(ctx)
+    }
+
+    /// Create the oracle provider registry; protocol admin only
+    pub fn initialize_oracle_registry(
+        ctx: Context<InitializeOracleRegistry>,
+        consensus_threshold: u8,
+    ) -> Result<()> {
+        let oracle_registry = &mut ctx.accounts.oracle_registry;
+
+        **oracle_registry = OracleRegistry::new(
+            ctx.accounts.authority.key(),
+            consensus_threshold,
+        )?;
+
+        Ok(())
+    }
+
+    /// Register an oracle provider; protocol admin only
+    pub fn add_oracle_provider(
+        ctx: Context<AddOracleProvider>,
+        provider_id: Pubkey,
+        provider_type: OracleType,
+        reliability_score: u16,
+    ) -> Result<()> {
+        let oracle_registry = &mut ctx.accounts.oracle_registry;
+
+        let provider = OracleProvider::new(provider_id, provider_type, reliability_score)?;
+        oracle_registry.add_oracle(provider)?;
+
+        Ok(())
+    }
+
+    /// Record an oracle report signed off-chain by a registered provider
+    pub fn submit_signed_report(
+        ctx: Context<SubmitSignedReport>,
+        oracle_provider: Pubkey,
+        winning_outcome: u8,
+        confidence_score: u16,
+        timestamp: i64,
+        evidence: Vec<u8>,
+    ) -> Result<()> {
+        signed_report::submit_signed_report(
+            ctx,
+            oracle_provider,
+            winning_outcome,
+            confidence_score,
+            timestamp,
+            evidence,
+        )
+    }
+
+    /// Create the global dispute configuration; protocol admin only
+    pub fn initialize_dispute_config(
+        ctx: Context<InitializeDisputeConfig>,
+        params: DisputeConfigParams,
+    ) -> Result<()> {
+        let dispute_config = &mut ctx.accounts.dispute_config;
+
+        **dispute_config = DisputeConfig::new(
+            ctx.accounts.authority.key(),
+            params,
+            ctx.bumps.dispute_config,
+        )?;
+
+        Ok(())
+    }
+
+    /// Update dispute parameters (protocol admin only); disputes already open keep the values they started with
+    pub fn update_dispute_config(
+        ctx: Context<UpdateDisputeConfig>,
+        params: DisputeConfigParams,
+    ) -> Result<()> {
+        let dispute_config = &mut ctx.accounts.dispute_config;
+
+        **dispute_config = DisputeConfig::new(
+            dispute_config.authority,
+            params,
+            dispute_config.bump,
+        )?;
+
+        Ok(())
+    }
+
     /// Submit a dispute for oracle data
     pub fn submit_dispute(
         ctx: Context<SubmitDispute>,
@@ -89,16 +505,43 @@ pub mod prediction_pump {
         let dispute = &mut ctx.accounts.dispute;
         let market = &mut ctx.accounts.market;
         let oracle_data = &mut ctx.accounts.oracle_data;
+        let dispute_config = &ctx.accounts.dispute_config;
         let disputer = ctx.accounts.disputer.key();
 
-        // Validate dispute submission
-        require!(!oracle_data.is_disputed, PredictionPumpError::AlreadyDisputed);
-        require!(market.status.is_settled, PredictionPumpError::MarketNotSettled);
-        require!(reason.len() <= 200, PredictionPumpError::DisputeReasonTooLong);
-        require!(stake_amount >= 1_000_000, PredictionPumpError::InsufficientDisputeStake); // 0.001 SOL minimum
+        // Validate dispute submission; a settled market can only be disputed over
+        // the report it was settled with, and only within the dispute window
+        market.status.check_dispute_window_at(Clock::get()?.unix_timestamp)?;
+        if let Some(settlement_data) = &market.settlement_data {
+            require!(
+                settlement_data.oracle_data_hash == oracle_data.data_hash,
+                PredictionPumpError::InvalidOracleData
+            );
+        }
+        require!(reason.len() <= MAX_DISPUTE_REASON_LEN, PredictionPumpError::DisputeReasonTooLong);
+        require!(stake_amount >= dispute_config.min_stake, PredictionPumpError::InsufficientDisputeStake);
 
-        // Mark oracle data as disputed
+        // Mark oracle data as disputed and pause claims until the ruling is final
+        // (fails if already disputed or ruled on)
         oracle_data.dispute()?;
+        market.status.is_disputed = true;
+
+        // Move the stake into the dispute escrow
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.disputer.to_account_info(),
+                    to: ctx.accounts.dispute_escrow.to_account_info(),
+                },
+            ),
+            stake_amount,
+        )?;
+
+        ctx.accounts.dispute_escrow.set_inner(DisputeEscrow {
+            dispute: dispute.key(),
+            amount: stake_amount,
+            bump: ctx.bumps.dispute_escrow,
+        });
 
         // Initialize dispute
         **dispute = Dispute::new(
@@ -107,119 +550,1034 @@ pub mod prediction_pump {
             disputer,
             reason,
             stake_amount,
+            dispute_config,
         )?;
 
         Ok(())
     }
 
-    /// Vote on a disputed oracle outcome
-    pub fn vote_on_dispute(
-        ctx: Context<VoteOnDispute>,
-        vote_outcome: u8,
-        vote_weight: u64,
+    /// Commit a hidden vote on a disputed oracle outcome. Vote weight equals the
+    /// SOL the voter locks in the dispute's vote escrow
+    pub fn commit_vote(
+        ctx: Context<CommitVote>,
+        commitment: [u8; 32],
+        lock_amount: u64,
     ) -> Result<()> {
         let dispute = &mut ctx.accounts.dispute;
+        let vote = &mut ctx.accounts.vote;
+        let vote_escrow = &mut ctx.accounts.vote_escrow;
         let voter = ctx.accounts.voter.key();
 
-        // Validate voting
+        // Validate voting (a second vote fails on the existing vote account)
         require!(!dispute.is_resolved, PredictionPumpError::DisputeAlreadyResolved);
         require!(dispute.voting_end_time > Clock::get()?.unix_timestamp, PredictionPumpError::VotingPeriodEnded);
-        require!(vote_weight > 0, PredictionPumpError::InvalidVoteWeight);
+        require!(lock_amount > 0, PredictionPumpError::InvalidVoteWeight);
+
+        // Lock the voter's stake
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.voter.to_account_info(),
+                    to: vote_escrow.to_account_info(),
+                },
+            ),
+            lock_amount,
+        )?;
+
+        vote_escrow.dispute = dispute.key();
+        vote_escrow.bump = ctx.bumps.vote_escrow;
+        vote_escrow.total_locked = vote_escrow
+            .total_locked
+            .checked_add(lock_amount)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+
+        // Record vote
+        **vote = DisputeVote::new(
+            dispute.key(),
+            voter,
+            dispute.round,
+            commitment,
+            lock_amount,
+            ctx.bumps.vote,
+        )?;
+        dispute.record_commit(lock_amount)?;
+
+        Ok(())
+    }
+
+    /// Reveal a committed vote during the reveal phase
+    pub fn reveal_vote(
+        ctx: Context<RevealVote>,
+        vote_outcome: u8,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        let dispute = &mut ctx.accounts.dispute;
+        let vote = &mut ctx.accounts.vote;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        // Validate reveal phase
+        require!(!dispute.is_resolved, PredictionPumpError::DisputeAlreadyResolved);
+        require!(dispute.voting_end_time <= current_time, PredictionPumpError::VotingPeriodNotEnded);
+        require!(dispute.reveal_end_time > current_time, PredictionPumpError::RevealPeriodEnded);
+
+        vote.reveal(vote_outcome, &salt)?;
+        dispute.record_reveal(vote_outcome, vote.weight)?;
+
+        Ok(())
+    }
+
+    /// Attach an evidence record to a dispute while its current round is still
+    /// open for votes. Anyone may submit evidence
+    pub fn add_evidence(
+        ctx: Context<AddEvidence>,
+        content_hash: [u8; 32],
+        uri: String,
+    ) -> Result<()> {
+        let dispute = &mut ctx.accounts.dispute;
+        let evidence = &mut ctx.accounts.evidence;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let index = dispute.record_evidence_at(current_time)?;
+        **evidence = DisputeEvidence::new(
+            dispute.key(),
+            ctx.accounts.submitter.key(),
+            index,
+            dispute.round,
+            content_hash,
+            uri,
+            current_time,
+            ctx.bumps.evidence,
+        )?;
+
+        emit!(EvidenceAddedEvent {
+            dispute: dispute.key(),
+            evidence: evidence.key(),
+            submitter: evidence.submitter,
+            index,
+            content_hash,
+        });
+
+        Ok(())
+    }
+
+    /// Unlock a voter's stake once their round is resolved; losing voters are slashed
+    pub fn withdraw_vote_stake(ctx: Context<WithdrawVoteStake>) -> Result<()> {
+        let dispute = &ctx.accounts.dispute;
+        let vote = &mut ctx.accounts.vote;
+        let vote_escrow = &mut ctx.accounts.vote_escrow;
+
+        require!(!vote.stake_withdrawn, PredictionPumpError::StakeAlreadyWithdrawn);
+
+        let payout = dispute.vote_principal(vote)?;
+        vote.stake_withdrawn = true;
+
+        let escrow_info = vote_escrow.to_account_info();
+        let voter_info = ctx.accounts.voter.to_account_info();
+
+        **escrow_info.try_borrow_mut_lamports()? = escrow_info
+            .lamports()
+            .checked_sub(payout)
+            .ok_or(PredictionPumpError::InsufficientVaultFunds)?;
+
+        **voter_info.try_borrow_mut_lamports()? = voter_info
+            .lamports()
+            .checked_add(payout)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+
+        vote_escrow.total_locked = vote_escrow.total_locked.saturating_sub(payout);
 
-        // Check if user already voted
-        require!(!dispute.votes.iter().any(|v| v.voter == voter), PredictionPumpError::AlreadyVoted);
+        Ok(())
+    }
 
-        // Add vote
-        let vote = DisputeVote::new(voter, vote_outcome, vote_weight)?;
-        dispute.add_vote(vote)?;
+    /// Claim a share of a final dispute's reward pool, as a winning voter (passing
+    /// the vote) or as a disputer whose dispute overturned the report. The receipt
+    /// account makes each claim one-time
+    pub fn claim_dispute_reward(ctx: Context<ClaimDisputeReward>) -> Result<()> {
+        let dispute = &ctx.accounts.dispute;
+        let vote_escrow = &mut ctx.accounts.vote_escrow;
+        let claimant = ctx.accounts.claimant.key();
+
+        require!(dispute.is_finalized, PredictionPumpError::DisputeNotFinalized);
+        require!(!dispute.bond_pending, PredictionPumpError::DisputeNotFinalized);
+
+        let (round, amount) = match &ctx.accounts.vote {
+            Some(vote) => (vote.round, dispute.vote_reward(vote)?),
+            None => {
+                require!(claimant == dispute.disputer, PredictionPumpError::Unauthorized);
+                (DISPUTER_RECEIPT_ROUND, dispute.disputer_reward()?)
+            }
+        };
+        require!(amount > 0, PredictionPumpError::NoPayoutAvailable);
+
+        let escrow_info = vote_escrow.to_account_info();
+        let claimant_info = ctx.accounts.claimant.to_account_info();
+
+        **escrow_info.try_borrow_mut_lamports()? = escrow_info
+            .lamports()
+            .checked_sub(amount)
+            .ok_or(PredictionPumpError::InsufficientVaultFunds)?;
+
+        **claimant_info.try_borrow_mut_lamports()? = claimant_info
+            .lamports()
+            .checked_add(amount)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+
+        vote_escrow.total_locked = vote_escrow.total_locked.saturating_sub(amount);
+
+        ctx.accounts.receipt.set_inner(RewardReceipt {
+            dispute: dispute.key(),
+            claimant,
+            round,
+            amount,
+            bump: ctx.bumps.receipt,
+        });
+
+        emit!(DisputeRewardClaimedEvent {
+            dispute: dispute.key(),
+            claimant,
+            round,
+            amount,
+        });
 
         Ok(())
     }
 
-    /// Resolve a dispute after voting period ends
+    /// Resolve the current round after its reveal period ends. The ruling becomes
+    /// binding once the appeal period passes without an appeal
     pub fn resolve_dispute(ctx: Context<ResolveDispute>) -> Result<()> {
         let dispute = &mut ctx.accounts.dispute;
-        let market = &mut ctx.accounts.market;
-        let oracle_data = &mut ctx.accounts.oracle_data;
 
         // Validate resolution
         require!(!dispute.is_resolved, PredictionPumpError::DisputeAlreadyResolved);
-        require!(dispute.voting_end_time <= Clock::get()?.unix_timestamp, PredictionPumpError::VotingPeriodNotEnded);
+        require!(dispute.reveal_end_time <= Clock::get()?.unix_timestamp, PredictionPumpError::RevealPeriodNotEnded);
+
+        // Calculate voting results
+        let resolution = dispute.calculate_resolution()?;
+
+        dispute.resolve(resolution)
+    }
+
+    /// Appeal the current round's ruling, escrowing double the previous round's
+    /// stake and opening a new voting round
+    pub fn appeal_dispute(ctx: Context<AppealDispute>) -> Result<()> {
+        let dispute = &mut ctx.accounts.dispute;
+        let appeal_escrow = &mut ctx.accounts.appeal_escrow;
+
+        let appealed_outcome = dispute
+            .resolution
+            .as_ref()
+            .ok_or(PredictionPumpError::DisputeNotResolved)?
+            .outcome
+            .clone();
+        let stake_amount = dispute.appeal_at(Clock::get()?.unix_timestamp)?;
+
+        // Move the appeal stake into the round's escrow
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.appellant.to_account_info(),
+                    to: appeal_escrow.to_account_info(),
+                },
+            ),
+            stake_amount,
+        )?;
+
+        appeal_escrow.set_inner(AppealEscrow {
+            dispute: dispute.key(),
+            appellant: ctx.accounts.appellant.key(),
+            round: dispute.round,
+            amount: stake_amount,
+            appealed_outcome,
+            bump: ctx.bumps.appeal_escrow,
+        });
+
+        Ok(())
+    }
+
+    /// Apply the final ruling once no further appeal is possible
+    pub fn finalize_dispute(ctx: Context<FinalizeDispute>) -> Result<()> {
+        let dispute = &mut ctx.accounts.dispute;
+        let market = &mut ctx.accounts.market;
+        let oracle_data = &mut ctx.accounts.oracle_data;
+        let oracle_registry = &mut ctx.accounts.oracle_registry;
+
+        dispute.finalize_at(Clock::get()?.unix_timestamp)?;
+        let resolution = dispute
+            .resolution
+            .clone()
+            .ok_or(PredictionPumpError::DisputeNotResolved)?;
+
+        // Adjust the reporting provider's reliability (no-op for unregistered reporters);
+        // a vote without quorum, or one voiding the market, says nothing about the report
+        if resolution.quorum_reached && resolution.outcome != DisputeOutcome::VoidMarket {
+            let upheld = resolution.outcome == DisputeOutcome::UpholdOriginal;
+            oracle_registry.record_dispute_outcome(oracle_data.oracle_provider, upheld);
+        }
+
+        // Update market and oracle data based on resolution
+        match resolution.outcome {
+            DisputeOutcome::UpholdOriginal => {
+                // Keep original oracle data
+                oracle_data.is_disputed = false;
+            }
+            DisputeOutcome::OverrideOutcome(new_outcome) => {
+                // Update oracle data with community decision
+                oracle_data.override_outcome(new_outcome);
+
+                // Re-point an already settled market at the new outcome
+                if market.status.is_settled {
+                    settlement::override_settlement(market, new_outcome, oracle_data.data_hash)?;
+                }
+            }
+            DisputeOutcome::VoidMarket => {
+                // The report no longer decides anything; every outcome token is refunded
+                oracle_data.is_disputed = false;
+                let outcome_supply = ctx
+                    .accounts
+                    .outcome_mint_0
+                    .supply
+                    .checked_add(ctx.accounts.outcome_mint_1.supply)
+                    .ok_or(PredictionPumpError::MathOverflow)?;
+                let collateral = ctx.accounts.collateral.balance(market, &ctx.accounts.market_vault.to_account_info())?;
+                settlement::void_market(market, outcome_supply, collateral, Clock::get()?.unix_timestamp)?;
+            }
+        }
+
+        // The ruling is binding: the report cannot be disputed again
+        oracle_data.is_ruled = true;
+
+        // Resume claims
+        market.status.is_disputed = false;
+
+        Ok(())
+    }
+
+    /// Release a resolved dispute's escrowed stake: returned if the outcome was
+    /// overturned, slashed to the oracle provider if it was upheld
+    pub fn settle_dispute_stake(ctx: Context<SettleDisputeStake>) -> Result<()> {
+        let dispute = &ctx.accounts.dispute;
+        let dispute_escrow = &ctx.accounts.dispute_escrow;
+
+        require!(dispute.is_finalized, PredictionPumpError::DisputeNotFinalized);
+        let resolution = dispute
+            .resolution
+            .as_ref()
+            .ok_or(PredictionPumpError::DisputeNotResolved)?;
+
+        let escrow_info = dispute_escrow.to_account_info();
+        let stake = dispute_escrow.amount;
+
+        match resolution.outcome {
+            DisputeOutcome::UpholdOriginal => {
+                // Slash the stake to the oracle provider whose report was upheld
+                let oracle_info = ctx.accounts.oracle_provider.to_account_info();
+
+                **escrow_info.try_borrow_mut_lamports()? = escrow_info
+                    .lamports()
+                    .checked_sub(stake)
+                    .ok_or(PredictionPumpError::InsufficientVaultFunds)?;
+
+                **oracle_info.try_borrow_mut_lamports()? = oracle_info
+                    .lamports()
+                    .checked_add(stake)
+                    .ok_or(PredictionPumpError::MathOverflow)?;
+            }
+            // The stake comes back when the escrow is closed; the disputer's reward
+            // is claimed from the vote escrow with claim_dispute_reward
+            DisputeOutcome::OverrideOutcome(_) | DisputeOutcome::VoidMarket => {}
+        }
+
+        Ok(())
+    }
+
+    /// Release an appeal stake after the dispute is final: refunded if the final
+    /// ruling differs from the one appealed, otherwise paid to the side that won
+    pub fn settle_appeal_stake(ctx: Context<SettleAppealStake>) -> Result<()> {
+        let dispute = &ctx.accounts.dispute;
+        let appeal_escrow = &ctx.accounts.appeal_escrow;
+
+        require!(dispute.is_finalized, PredictionPumpError::DisputeNotFinalized);
+        let resolution = dispute
+            .resolution
+            .as_ref()
+            .ok_or(PredictionPumpError::DisputeNotResolved)?;
+
+        // A successful appeal gets its stake back when the escrow is closed
+        if resolution.outcome != appeal_escrow.appealed_outcome {
+            return Ok(());
+        }
+
+        let recipient_info = match resolution.outcome {
+            DisputeOutcome::UpholdOriginal => ctx.accounts.oracle_provider.to_account_info(),
+            DisputeOutcome::OverrideOutcome(_) | DisputeOutcome::VoidMarket => {
+                ctx.accounts.disputer.to_account_info()
+            }
+        };
+        let escrow_info = appeal_escrow.to_account_info();
+
+        **escrow_info.try_borrow_mut_lamports()? = escrow_info
+            .lamports()
+            .checked_sub(appeal_escrow.amount)
+            .ok_or(PredictionPumpError::InsufficientVaultFunds)?;
+
+        **recipient_info.try_borrow_mut_lamports()? = recipient_info
+            .lamports()
+            .checked_add(appeal_escrow.amount)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = ProtocolConfig::LEN,
+        seeds = [b"protocol_config"],
+        bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, program::PredictionPump>,
+
+    /// Upgrade state of this program; its upgrade authority must sign, so the
+    /// config cannot be claimed by whoever initializes first
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ PredictionPumpError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        has_one = admin @ PredictionPumpError::Unauthorized
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TransferAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        has_one = admin @ PredictionPumpError::Unauthorized
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Must match the admin nominated by `transfer_admin`
+    pub new_admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeOracleRegistry<'info> {
+    /// Only the protocol admin manages the oracle registry
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = protocol_config.admin == authority.key() @ PredictionPumpError::Unauthorized
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Created at the registry address the protocol config names
+    #[account(
+        init,
+        payer = authority,
+        space = OracleRegistry::LEN,
+        seeds = [b"oracle_registry"],
+        bump,
+        address = protocol_config.oracle_registry @ PredictionPumpError::UnauthorizedOracle
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddOracleProvider<'info> {
+    /// Only the protocol admin manages the oracle registry
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = protocol_config.admin == authority.key() @ PredictionPumpError::Unauthorized
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        address = protocol_config.oracle_registry @ PredictionPumpError::UnauthorizedOracle
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeDisputeConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = DisputeConfig::LEN,
+        seeds = [b"dispute_config"],
+        bump
+    )]
+    pub dispute_config: Account<'info, DisputeConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Only the protocol admin manages the dispute parameters
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = protocol_config.admin == authority.key() @ PredictionPumpError::Unauthorized
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateDisputeConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"dispute_config"],
+        bump = dispute_config.bump
+    )]
+    pub dispute_config: Account<'info, DisputeConfig>,
+
+    pub authority: Signer<'info>,
+
+    /// Only the protocol admin manages the dispute parameters
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = protocol_config.admin == authority.key() @ PredictionPumpError::Unauthorized
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitDispute<'info> {
+    #[account(
+        init,
+        payer = disputer,
+        space = Dispute::LEN
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        has_one = market
+    )]
+    pub oracle_data: Account<'info, OracleData>,
+
+    #[account(
+        seeds = [b"dispute_config"],
+        bump = dispute_config.bump
+    )]
+    pub dispute_config: Account<'info, DisputeConfig>,
+
+    /// Escrow holding the dispute stake until the dispute is resolved
+    #[account(
+        init,
+        payer = disputer,
+        space = DisputeEscrow::LEN,
+        seeds = [b"dispute_escrow", dispute.key().as_ref()],
+        bump
+    )]
+    pub dispute_escrow: Account<'info, DisputeEscrow>,
+
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(SubmitDispute::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SettleDisputeStake<'info> {
+    #[account(
+        has_one = market,
+        has_one = oracle_data,
+        has_one = disputer
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    /// Closed to the disputer once settled, returning rent (and the stake if not slashed)
+    #[account(
+        mut,
+        seeds = [b"dispute_escrow", dispute.key().as_ref()],
+        bump = dispute_escrow.bump,
+        has_one = dispute,
+        close = disputer
+    )]
+    pub dispute_escrow: Account<'info, DisputeEscrow>,
+
+    pub market: Account<'info, Market>,
+
+    pub oracle_data: Account<'info, OracleData>,
+
+    #[account(mut)]
+    /// CHECK: Must be the dispute's disputer
+    pub disputer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = oracle_data.oracle_provider @ PredictionPumpError::UnauthorizedOracle
+    )]
+    /// CHECK: Receives slashed stakes; must be the disputed report's provider
+    pub oracle_provider: UncheckedAccount<'info>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(SettleDisputeStake::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct CommitVote<'info> {
+    #[account(mut, has_one = market)]
+    pub dispute: Account<'info, Dispute>,
+
+    /// Disputed market, whose pause bits apply
+    pub market: Account<'info, Market>,
+
+    /// One vote account per voter; its existence enforces a single vote
+    #[account(
+        init,
+        payer = voter,
+        space = DisputeVote::LEN,
+        seeds = [b"vote", dispute.key().as_ref(), voter.key().as_ref(), &[dispute.round]],
+        bump
+    )]
+    pub vote: Account<'info, DisputeVote>,
+
+    /// Escrow holding all stake locked by this dispute's voters
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = VoteEscrow::LEN,
+        seeds = [b"vote_escrow", dispute.key().as_ref()],
+        bump
+    )]
+    pub vote_escrow: Account<'info, VoteEscrow>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(CommitVote::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct RevealVote<'info> {
+    #[account(mut, has_one = market)]
+    pub dispute: Account<'info, Dispute>,
+
+    /// Disputed market, whose pause bits apply
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"vote", dispute.key().as_ref(), voter.key().as_ref(), &[dispute.round]],
+        bump = vote.bump,
+        has_one = dispute,
+        has_one = voter
+    )]
+    pub vote: Account<'info, DisputeVote>,
+
+    pub voter: Signer<'info>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(RevealVote::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct AddEvidence<'info> {
+    #[account(mut, has_one = market)]
+    pub dispute: Account<'info, Dispute>,
+
+    /// Disputed market, whose pause bits apply
+    pub market: Account<'info, Market>,
+
+    /// Evidence records are numbered in submission order
+    #[account(
+        init,
+        payer = submitter,
+        space = DisputeEvidence::LEN,
+        seeds = [b"evidence", dispute.key().as_ref(), &dispute.evidence_count.to_le_bytes()],
+        bump
+    )]
+    pub evidence: Account<'info, DisputeEvidence>,
+
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(AddEvidence::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawVoteStake<'info> {
+    #[account(has_one = market)]
+    pub dispute: Account<'info, Dispute>,
+
+    /// Disputed market, whose pause bits apply
+    pub market: Account<'info, Market>,
+
+    /// Vote from any round of the dispute
+    #[account(
+        mut,
+        seeds = [b"vote", dispute.key().as_ref(), voter.key().as_ref(), &[vote.round]],
+        bump = vote.bump,
+        has_one = dispute,
+        has_one = voter
+    )]
+    pub vote: Account<'info, DisputeVote>,
+
+    #[account(
+        mut,
+        seeds = [b"vote_escrow", dispute.key().as_ref()],
+        bump = vote_escrow.bump,
+        has_one = dispute
+    )]
+    pub vote_escrow: Account<'info, VoteEscrow>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(WithdrawVoteStake::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimDisputeReward<'info> {
+    #[account(has_one = market)]
+    pub dispute: Account<'info, Dispute>,
+
+    /// Disputed market, whose pause bits apply
+    pub market: Account<'info, Market>,
+
+    /// The claimant's vote; omitted when the disputer claims
+    #[account(
+        seeds = [b"vote", dispute.key().as_ref(), claimant.key().as_ref(), &[vote.round]],
+        bump = vote.bump,
+        has_one = dispute
+    )]
+    pub vote: Option<Account<'info, DisputeVote>>,
+
+    #[account(
+        mut,
+        seeds = [b"vote_escrow", dispute.key().as_ref()],
+        bump = vote_escrow.bump,
+        has_one = dispute
+    )]
+    pub vote_escrow: Account<'info, VoteEscrow>,
+
+    /// One receipt per claimant and round; a second claim fails on the existing account
+    #[account(
+        init,
+        payer = claimant,
+        space = RewardReceipt::LEN,
+        seeds = [
+            b"reward_receipt",
+            dispute.key().as_ref(),
+            claimant.key().as_ref(),
+            &[vote.as_ref().map_or(DISPUTER_RECEIPT_ROUND, |vote| vote.round)]
+        ],
+        bump
+    )]
+    pub receipt: Account<'info, RewardReceipt>,
+
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(ClaimDisputeReward::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    /// Anyone may resolve a round once its reveal period has ended
+    #[account(mut, has_one = market)]
+    pub dispute: Account<'info, Dispute>,
+
+    /// Disputed market, whose pause bits apply
+    pub market: Account<'info, Market>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(ResolveDispute::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct AppealDispute<'info> {
+    #[account(mut, has_one = market)]
+    pub dispute: Account<'info, Dispute>,
+
+    /// Disputed market, whose pause bits apply
+    pub market: Account<'info, Market>,
+
+    /// Escrow holding the appeal stake for the round it opens
+    #[account(
+        init,
+        payer = appellant,
+        space = AppealEscrow::LEN,
+        seeds = [b"appeal_escrow", dispute.key().as_ref(), &[dispute.round + 1]],
+        bump
+    )]
+    pub appeal_escrow: Account<'info, AppealEscrow>,
+
+    #[account(mut)]
+    pub appellant: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(AppealDispute::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeDispute<'info> {
+    /// Anyone may finalize; the ruling only touches the dispute's own market and report
+    #[account(
+        mut,
+        has_one = market,
+        has_one = oracle_data
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        has_one = market
+    )]
+    pub oracle_data: Account<'info, OracleData>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(FinalizeDispute::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Registry whose provider reliability scores track dispute outcomes
+    #[account(
+        mut,
+        address = protocol_config.oracle_registry @ PredictionPumpError::UnauthorizedOracle
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
 
-        // Calculate voting results
-        let resolution = dispute.calculate_resolution()?;
+    /// Vault whose collateral is refunded if the market is voided
+    #[account(
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market_vault.bump
+    )]
+    pub market_vault: Account<'info, MarketVault>,
 
-        // Apply resolution
-        dispute.resolve(resolution.clone())?;
+    /// Outcome mints, whose supplies split the refund of a voided market
+    #[account(address = market.outcome_tokens[0] @ PredictionPumpError::InvalidOutcomeMint)]
+    pub outcome_mint_0: InterfaceAccount<'info, token_interface::Mint>,
 
-        // Update market and oracle data based on resolution
-        match resolution.outcome {
-            DisputeOutcome::UpholdOriginal => {
-                // Keep original oracle data
-                oracle_data.is_disputed = false;
-            }
-            DisputeOutcome::OverrideOutcome(new_outcome) => {
-                // Update oracle data with community decision
-                oracle_data.winning_outcome = new_outcome;
-                oracle_data.is_disputed = false;
-                
-                // Update market settlement data
-                if let Some(ref mut settlement_data) = market.settlement_data {
-                    settlement_data.winning_outcome = new_outcome;
-                }
-            }
-        }
+    #[account(address = market.outcome_tokens[1] @ PredictionPumpError::InvalidOutcomeMint)]
+    pub outcome_mint_1: InterfaceAccount<'info, token_interface::Mint>,
 
-        Ok(())
-    }
+    /// Collateral mint and vault token account; omitted for SOL markets
+    pub collateral: Collateral<'info>,
 }
 
 #[derive(Accounts)]
-pub struct Initialize {}
-
-#[derive(Accounts)]
-pub struct SubmitDispute<'info> {
+pub struct SettleAppealStake<'info> {
     #[account(
-        init,
-        payer = disputer,
-        space = Dispute::LEN
+        has_one = market,
+        has_one = oracle_data,
+        has_one = disputer
     )]
     pub dispute: Account<'info, Dispute>,
 
-    #[account(mut)]
+    /// Disputed market, whose pause bits apply
     pub market: Account<'info, Market>,
 
-    #[account(mut)]
+    /// Closed to the appellant once settled, returning rent (and the stake if refunded)
+    #[account(
+        mut,
+        seeds = [b"appeal_escrow", dispute.key().as_ref(), &[appeal_escrow.round]],
+        bump = appeal_escrow.bump,
+        has_one = dispute,
+        has_one = appellant,
+        close = appellant
+    )]
+    pub appeal_escrow: Account<'info, AppealEscrow>,
+
     pub oracle_data: Account<'info, OracleData>,
 
     #[account(mut)]
-    pub disputer: Signer<'info>,
+    /// CHECK: Must be the appeal escrow's appellant
+    pub appellant: UncheckedAccount<'info>,
 
-    pub system_program: Program<'info, System>,
+    #[account(mut)]
+    /// CHECK: Receives lost appeal stakes when the outcome was overturned; must be the dispute's disputer
+    pub disputer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = oracle_data.oracle_provider @ PredictionPumpError::UnauthorizedOracle
+    )]
+    /// CHECK: Receives lost appeal stakes when the outcome was upheld; must be the disputed report's provider
+    pub oracle_provider: UncheckedAccount<'info>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(SettleAppealStake::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
 }
 
 #[derive(Accounts)]
-pub struct VoteOnDispute<'info> {
-    #[account(mut)]
-    pub dispute: Account<'info, Dispute>,
+pub struct SetResolutionCondition<'info> {
+    #[account(
+        mut,
+        has_one = creator @ PredictionPumpError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
 
-    #[account(mut)]
-    pub voter: Signer<'info>,
+    pub creator: Signer<'info>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(SetResolutionCondition::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
 }
 
 #[derive(Accounts)]
-pub struct ResolveDispute<'info> {
-    #[account(mut)]
-    pub dispute: Account<'info, Dispute>,
+pub struct SetOptimisticConfig<'info> {
+    #[account(
+        mut,
+        has_one = creator @ PredictionPumpError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
 
-    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(SetOptimisticConfig::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetDynamicFee<'info> {
+    #[account(
+        mut,
+        has_one = creator @ PredictionPumpError::Unauthorized
+    )]
     pub market: Account<'info, Market>,
 
-    #[account(mut)]
-    pub oracle_data: Account<'info, OracleData>,
+    pub creator: Signer<'info>,
 
-    /// CHECK: Authority validation handled in instruction
-    pub resolver: UncheckedAccount<'info>,
+    /// Supplies the fee bounds; rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(SetDynamicFee::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
 }
 
 #[derive(Accounts)]
@@ -238,24 +1596,59 @@ pub struct CreateMarket<'info> {
     /// CHECK: Oracle validation is handled by the market logic
     pub oracle_source: UncheckedAccount<'info>,
 
-    // Outcome token mints (required: first 2)
+    /// Supplies fee bounds, the fee split and the creation fee
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        has_one = treasury @ PredictionPumpError::Unauthorized,
+        constraint = !protocol_config.is_paused(CreateMarket::PAUSE_FLAG, 0) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(mut)]
+    /// CHECK: Must be the treasury recorded in the protocol config
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Holds the market's collateral and fees, and signs for its outcome mints
     #[account(
         init,
         payer = creator,
-        mint::decimals = 6,
-        mint::authority = market
+        space = MarketVault::LEN,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump
     )]
-    pub outcome_mint_0: Account<'info, Mint>,
+    pub market_vault: Account<'info, MarketVault>,
+
+    // Outcome token mints (required: first 2), created by the handler
+    #[account(mut)]
+    pub outcome_mint_0: Signer<'info>,
+
+    #[account(mut)]
+    pub outcome_mint_1: Signer<'info>,
 
+    /// Liquidity provider shares of the market's pool
     #[account(
         init,
         payer = creator,
+        seeds = [b"lp_mint", market.key().as_ref()],
+        bump,
         mint::decimals = 6,
-        mint::authority = market
+        mint::authority = market_vault
     )]
-    pub outcome_mint_1: Account<'info, Mint>,
+    pub lp_mint: Account<'info, Mint>,
+
+    /// SPL collateral mint (e.g. USDC); omit to trade in native SOL
+    pub collateral_mint: Option<Account<'info, Mint>>,
+
+    #[account(mut)]
+    /// CHECK: Created here as the market vault's associated token account for the collateral mint
+    pub vault_token_account: Option<UncheckedAccount<'info>>,
+
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
 
     pub token_program: Program<'info, Token>,
+    /// Token or Token-2022; Token-2022 outcome mints carry their name in a metadata extension
+    pub outcome_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -271,34 +1664,188 @@ pub struct Market {
     pub resolution_date: i64,
     pub oracle_source: Pubkey,
     pub outcome_tokens: Vec<Pubkey>,
-    pub bonding_curve_params: BondingCurveParams,
+    pub collateral_mint: Option<Pubkey>, // None for native SOL
+    pub collateral_decimals: u8,
+    pub bonding_curve_params: BondingCurveParams, // prices in base units of the collateral
+    pub dynamic_fee: Option<DynamicFee>, // overrides bonding_curve_params.fee_rate when set
+    pub fee_schedule: FeeSchedule, // copied from ProtocolConfig when the market is created
+    pub fees: MarketFees,
+    pub liquidity: LiquidityPool,
+    pub creator_rewards: CreatorRewards,
     pub total_volume: u64,
+    pub creator_bond: u64, // lamports held in the vault until release_creator_bond
     pub status: MarketStatus,
     pub settlement_data: Option<SettlementData>,
+    pub resolution_condition: Option<ResolutionCondition>,
+    pub optimistic_config: Option<OptimisticConfig>,
+    pub paused: u8, // PAUSE_* bits applied on top of the protocol-wide bits
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
 pub struct MarketStatus {
     pub is_active: bool,
     pub is_settled: bool,
+    pub is_disputed: bool, // claims are paused while the reported outcome is disputed
+    pub is_voided: bool, // voided as malformed by dispute; settled with no winning outcome
     pub winning_outcome: Option<u8>,
     pub settlement_timestamp: Option<i64>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
 pub struct BondingCurveParams {
-    pub initial_price: u64,
+    pub initial_price: u64, // base units of the market's collateral (lamports for SOL)
     pub curve_steepness: u64,
     pub max_supply: u64,
     pub fee_rate: u16, // basis points (e.g., 100 = 1%)
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct DynamicFeeParams {
+    pub min_fee_rate: u16,
+    pub max_fee_rate: u16,
+    pub target_volume: u64, // recent volume at which the fee reaches max_fee_rate
+    pub half_life: i64, // seconds for the volume EMA to decay by half
+}
+
+/// Fee rate that rises with a market's recent trading volume, so hot markets
+/// pay more to trade in and out
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct DynamicFee {
+    pub min_fee_rate: u16,
+    pub max_fee_rate: u16,
+    pub target_volume: u64,
+    pub half_life: i64,
+    pub volume_ema: u64, // time-decayed trading volume as of last_trade
+    pub last_trade: i64,
+}
+
+/// How a trading fee is divided; shares are basis points of the fee and sum to 10000
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct FeeSchedule {
+    pub creator_bps: u16,
+    pub protocol_bps: u16,
+    pub lp_bps: u16,
+}
+
+/// Running totals of a market's trading fees by recipient
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct MarketFees {
+    pub creator_accrued: u64,
+    pub creator_claimed: u64,
+    pub protocol_accrued: u64,
+    pub protocol_claimed: u64,
+    pub lp_accrued: u64, // LP share credited to the pool; with no LPs it stays as collateral
+    pub lp_claimed: u64,
+}
+
+/// Collateral deposited by liquidity providers, kept apart from trading collateral
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct LiquidityPool {
+    pub collateral: u64,
+    pub total_shares: u64, // LP tokens outstanding
+    pub fee_per_share: u128, // cumulative LP fees per share, scaled by FEE_PER_SHARE_PRECISION
+}
+
+/// Creator fees owed to staked creator shares
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct CreatorRewards {
+    pub total_staked: u64,
+    pub reward_per_token: u128, // cumulative fees per staked share, scaled by FEE_PER_SHARE_PRECISION
+    pub undistributed: u64, // fees that arrived while nothing was staked, owed to the creator
+}
+
+/// A holder's staked creator shares, stored at `["creator_stake", market, owner]`
+#[account]
+pub struct CreatorStake {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub staked: u64,
+    pub reward_debt: u128, // staked * reward_per_token at the last checkpoint
+    pub rewards_owed: u64,
+    pub bump: u8,
+}
+
+/// A provider's share of a market pool, stored at `["lp_position", market, owner]`.
+/// Fees follow the position that deposited, not later holders of its LP tokens
+#[account]
+pub struct LpPosition {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub shares: u64,
+    pub reward_debt: u128, // shares * fee_per_share at the last checkpoint
+    pub fees_owed: u64, // fees settled at checkpoints but not yet withdrawn
+    pub unlocks_at: i64, // shares cannot be withdrawn before this time
+    pub bump: u8,
+}
+
+/// Allowed range for a market's trading fee rate, in basis points
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct FeeBounds {
+    pub min_fee_rate: u16,
+    pub max_fee_rate: u16,
+}
+
+/// Global protocol settings, stored at `["protocol_config"]`
+#[account]
+pub struct ProtocolConfig {
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>, // nominated by transfer_admin, not yet accepted
+    pub guardian: Pubkey, // may set pause bits alongside the admin
+    pub paused: u8, // PAUSE_* bits applying to every market
+    pub treasury: Pubkey,
+    pub fee_schedule: FeeSchedule, // protocol_bps is the protocol's cut of each trading fee
+    pub fee_bounds: FeeBounds,
+    pub creation_fee: u64, // lamports paid to the treasury per market
+    pub creator_bond: u64, // refundable lamports escrowed per market, slashed if it is voided
+    pub oracle_registry: Pubkey, // registry whose providers may submit signed reports
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ProtocolConfigParams {
+    pub guardian: Pubkey,
+    pub treasury: Pubkey,
+    pub fee_schedule: FeeSchedule,
+    pub fee_bounds: FeeBounds,
+    pub creation_fee: u64,
+    pub creator_bond: u64,
+    pub oracle_registry: Pubkey,
+}
+
+/// Program-owned vault holding a market's collateral, stored at `["market_vault", market]`
+#[account]
+pub struct MarketVault {
+    pub market: Pubkey,
+    pub bump: u8,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
 pub struct SettlementData {
     pub winning_outcome: u8,
     pub settlement_timestamp: i64,
     pub oracle_data_hash: [u8; 32],
     pub total_payout: u64,
+    pub winning_supply: u64, // winning mint supply snapshotted at the first claim, 0 until then
+    pub total_claimed: u64,
+}
+
+/// Maps a numeric oracle result to a binary outcome: outcome 0 (YES) when
+/// `result <comparator> threshold` holds, outcome 1 (NO) otherwise
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ResolutionCondition {
+    pub comparator: Comparator,
+    pub threshold: SwitchboardDecimal,
+    pub max_staleness: i64, // seconds
+    pub min_oracle_results: u32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum Comparator {
+    GreaterThan,
+    GreaterThanOrEqual,
+    LessThan,
+    LessThanOrEqual,
+    Equal,
 }
 
 // Oracle integration structures - minimal implementation for task 5.1
@@ -333,8 +1880,10 @@ pub struct OracleData {
     pub winning_outcome: u8,
     pub confidence_score: u16,
     pub timestamp: i64,
+    pub evidence: Vec<u8>,
     pub data_hash: [u8; 32],
     pub is_disputed: bool,
+    pub is_ruled: bool, // a dispute over this report was finalized
 }
 
 impl Market {
@@ -344,10 +1893,21 @@ impl Market {
         8 + // resolution_date
         32 + // oracle_source
         4 + (32 * 2) + // outcome_tokens (binary only)
+        1 + 32 + // collateral_mint (Option)
+        1 + // collateral_decimals
         BondingCurveParams::LEN + // bonding_curve_params
+        1 + DynamicFee::LEN + // dynamic_fee (Option)
+        FeeSchedule::LEN + // fee_schedule
+        MarketFees::LEN + // fees
+        LiquidityPool::LEN + // liquidity
+        CreatorRewards::LEN + // creator_rewards
         8 + // total_volume
+        8 + // creator_bond
         MarketStatus::LEN + // status
-        1 + SettlementData::LEN; // settlement_data (Option)
+        1 + SettlementData::LEN + // settlement_data (Option)
+        1 + ResolutionCondition::LEN + // resolution_condition (Option)
+        1 + OptimisticConfig::LEN + // optimistic_config (Option)
+        1; // paused
 
     pub fn new(
         creator: Pubkey,
@@ -356,38 +1916,559 @@ impl Market {
         oracle_source: Pubkey,
         outcome_tokens: Vec<Pubkey>,
         bonding_curve_params: BondingCurveParams,
+        protocol_config: &ProtocolConfig,
     ) -> Result<Self> {
         // Validation
         require!(description.len() <= 100, PredictionPumpError::DescriptionTooLong);
         require!(outcome_tokens.len() >= 2, PredictionPumpError::InsufficientOutcomes);
         require!(outcome_tokens.len() <= 2, PredictionPumpError::TooManyOutcomes);
         require!(resolution_date > Clock::get()?.unix_timestamp, PredictionPumpError::InvalidResolutionDate);
-        require!(bonding_curve_params.fee_rate <= 1000, PredictionPumpError::FeeTooHigh); // Max 10%
+        protocol_config.fee_bounds.validate_fee_rate(bonding_curve_params.fee_rate)?;
+
+        Ok(Market {
+            creator,
+            description,
+            resolution_date,
+            oracle_source,
+            outcome_tokens,
+            collateral_mint: None,
+            collateral_decimals: NATIVE_SOL_DECIMALS,
+            bonding_curve_params,
+            dynamic_fee: None,
+            fee_schedule: protocol_config.fee_schedule.clone(),
+            fees: MarketFees::default(),
+            liquidity: LiquidityPool::default(),
+            creator_rewards: CreatorRewards::default(),
+            total_volume: 0,
+            creator_bond: protocol_config.creator_bond,
+            status: MarketStatus {
+                is_active: false,
+                is_settled: false,
+                is_disputed: false,
+                is_voided: false,
+                winning_outcome: None,
+                settlement_timestamp: None,
+            },
+            settlement_data: None,
+            resolution_condition: None,
+            optimistic_config: None,
+            paused: 0,
+        })
+    }
+
+    /// Trading runs until the resolution date, and stops once settled or disputed
+    pub fn require_trading_open(&self, current_time: i64) -> Result<()> {
+        require!(!self.status.is_settled, PredictionPumpError::MarketAlreadySettled);
+        require!(!self.status.is_disputed, PredictionPumpError::MarketDisputed);
+        require!(current_time < self.resolution_date, PredictionPumpError::TradingClosed);
+        Ok(())
+    }
+
+    /// Resolution and fee settings are fixed once the market has traded
+    pub fn require_untraded(&self) -> Result<()> {
+        require!(!self.status.is_settled, PredictionPumpError::MarketAlreadySettled);
+        require!(self.total_volume == 0, PredictionPumpError::MarketAlreadyTraded);
+        Ok(())
+    }
+
+    pub fn outcome_mint(&self, outcome: u8) -> Result<Pubkey> {
+        self.outcome_tokens
+            .get(outcome as usize)
+            .copied()
+            .ok_or(PredictionPumpError::InvalidOutcomeMint.into())
+    }
+
+    /// Fee rate charged on a trade at `current_time`
+    pub fn fee_rate_at(&self, current_time: i64) -> u16 {
+        match &self.dynamic_fee {
+            Some(dynamic_fee) => dynamic_fee.fee_rate_at(current_time),
+            None => self.bonding_curve_params.fee_rate,
+        }
+    }
+
+    /// Curve parameters to quote a trade with, carrying the current fee rate
+    pub fn curve_params_at(&self, current_time: i64) -> BondingCurveParams {
+        BondingCurveParams {
+            fee_rate: self.fee_rate_at(current_time),
+            ..self.bonding_curve_params.clone()
+        }
+    }
+
+    /// Add a trade's volume and split its fee
+    pub fn record_trade(&mut self, volume: u64, fee: u64, current_time: i64) -> Result<()> {
+        self.total_volume = self
+            .total_volume
+            .checked_add(volume)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        if let Some(dynamic_fee) = &mut self.dynamic_fee {
+            dynamic_fee.record_volume_at(volume, current_time)?;
+        }
+
+        let (creator_fee, lp_fee) = self.fees.accrue(fee, &self.fee_schedule)?;
+        self.creator_rewards.distribute(creator_fee)?;
+        if self.liquidity.distribute_fee(lp_fee)? {
+            self.fees.lp_accrued = self
+                .fees
+                .lp_accrued
+                .checked_add(lp_fee)
+                .ok_or(PredictionPumpError::MathOverflow)?;
+        }
+        Ok(())
+    }
+
+    /// Collateral backing outcome tokens out of the vault's available balance,
+    /// net of fees owed and LP deposits
+    pub fn collateral_in(&self, vault_balance: u64) -> u64 {
+        vault_balance
+            .saturating_sub(self.fees.reserved())
+            .saturating_sub(self.liquidity.collateral)
+    }
+}
+
+impl FeeSchedule {
+    pub const LEN: usize = 2 + // creator_bps
+        2 + // protocol_bps
+        2; // lp_bps
+
+    pub fn validate(&self) -> Result<()> {
+        let total = self.creator_bps as u32 + self.protocol_bps as u32 + self.lp_bps as u32;
+        require!(total == 10000, PredictionPumpError::InvalidFeeSchedule);
+        Ok(())
+    }
+
+    /// Split a fee into (creator, protocol, lp) shares; rounding dust goes to LPs
+    pub fn split(&self, fee: u64) -> Result<(u64, u64, u64)> {
+        let share = |bps: u16| -> Result<u64> {
+            Ok((fee as u128)
+                .checked_mul(bps as u128)
+                .ok_or(PredictionPumpError::MathOverflow)?
+                .checked_div(10000)
+                .ok_or(PredictionPumpError::MathOverflow)? as u64)
+        };
+        let creator = share(self.creator_bps)?;
+        let protocol = share(self.protocol_bps)?;
+        let lp = fee
+            .checked_sub(creator)
+            .and_then(|rest| rest.checked_sub(protocol))
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        Ok((creator, protocol, lp))
+    }
+}
+
+impl MarketFees {
+    pub const LEN: usize = 8 + 8 + // creator_accrued, creator_claimed
+        8 + 8 + // protocol_accrued, protocol_claimed
+        8 + 8; // lp_accrued, lp_claimed
+
+    /// Credit the creator and protocol shares of a fee, returning the
+    /// (creator, LP) shares for distribution to their holders
+    pub fn accrue(&mut self, fee: u64, schedule: &FeeSchedule) -> Result<(u64, u64)> {
+        let (creator, protocol, lp) = schedule.split(fee)?;
+        self.creator_accrued = self
+            .creator_accrued
+            .checked_add(creator)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        self.protocol_accrued = self
+            .protocol_accrued
+            .checked_add(protocol)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        Ok((creator, lp))
+    }
+
+    pub fn creator_claimable(&self) -> u64 {
+        self.creator_accrued.saturating_sub(self.creator_claimed)
+    }
+
+    pub fn protocol_claimable(&self) -> u64 {
+        self.protocol_accrued.saturating_sub(self.protocol_claimed)
+    }
+
+    pub fn lp_claimable(&self) -> u64 {
+        self.lp_accrued.saturating_sub(self.lp_claimed)
+    }
+
+    /// Fees held in the vault that are not collateral
+    pub fn reserved(&self) -> u64 {
+        self.creator_claimable()
+            .saturating_add(self.protocol_claimable())
+            .saturating_add(self.lp_claimable())
+    }
+
+    /// Record creator fees paid out to a share holder
+    pub fn pay_creator(&mut self, amount: u64) -> Result<()> {
+        require!(amount <= self.creator_claimable(), PredictionPumpError::InsufficientVaultFunds);
+        self.creator_claimed = self
+            .creator_claimed
+            .checked_add(amount)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Mark the protocol's unclaimed fees as paid, returning the amount
+    pub fn claim_protocol(&mut self) -> Result<u64> {
+        let amount = self.protocol_claimable();
+        require!(amount > 0, PredictionPumpError::NoFeesToClaim);
+        self.protocol_claimed = self.protocol_accrued;
+        Ok(amount)
+    }
+}
+
+impl FeeBounds {
+    pub const LEN: usize = 2 + // min_fee_rate
+        2; // max_fee_rate
+
+    /// Bounds must be ordered, and a fee can never take a whole trade
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.min_fee_rate <= self.max_fee_rate && self.max_fee_rate < 10000,
+            PredictionPumpError::InvalidProtocolConfig
+        );
+        Ok(())
+    }
+
+    pub fn validate_fee_rate(&self, fee_rate: u16) -> Result<()> {
+        require!(
+            (self.min_fee_rate..=self.max_fee_rate).contains(&fee_rate),
+            PredictionPumpError::FeeTooHigh
+        );
+        Ok(())
+    }
+}
+
+impl ProtocolConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // admin
+        1 + 32 + // pending_admin (Option)
+        32 + // guardian
+        1 + // paused
+        32 + // treasury
+        FeeSchedule::LEN + // fee_schedule
+        FeeBounds::LEN + // fee_bounds
+        8 + // creation_fee
+        8 + // creator_bond
+        32 + // oracle_registry
+        1; // bump
+
+    pub fn new(admin: Pubkey, params: ProtocolConfigParams, bump: u8) -> Result<Self> {
+        params.fee_schedule.validate()?;
+        params.fee_bounds.validate()?;
+
+        Ok(ProtocolConfig {
+            admin,
+            pending_admin: None,
+            guardian: params.guardian,
+            paused: 0,
+            treasury: params.treasury,
+            fee_schedule: params.fee_schedule,
+            fee_bounds: params.fee_bounds,
+            creation_fee: params.creation_fee,
+            creator_bond: params.creator_bond,
+            oracle_registry: params.oracle_registry,
+            bump,
+        })
+    }
+
+    /// Whether `flag` is paused protocol-wide or by a market's own bits
+    pub fn is_paused(&self, flag: u8, market_paused: u8) -> bool {
+        (self.paused | market_paused) & flag != 0
+    }
+
+    pub fn can_pause(&self, signer: &Pubkey) -> bool {
+        *signer == self.guardian || *signer == self.admin
+    }
+
+    /// Complete a two-step admin transfer
+    pub fn accept_admin(&mut self, new_admin: Pubkey) -> Result<()> {
+        require!(
+            self.pending_admin == Some(new_admin),
+            PredictionPumpError::Unauthorized
+        );
+        self.admin = new_admin;
+        self.pending_admin = None;
+        Ok(())
+    }
+}
+
+/// Rewards earned by `shares` at an accumulator value scaled by FEE_PER_SHARE_PRECISION
+pub fn accumulated_rewards(shares: u64, per_share: u128) -> Result<u128> {
+    Ok((shares as u128)
+        .checked_mul(per_share)
+        .ok_or(PredictionPumpError::MathOverflow)?
+        / FEE_PER_SHARE_PRECISION)
+}
+
+impl LiquidityPool {
+    pub const LEN: usize = 8 + // collateral
+        8 + // total_shares
+        16; // fee_per_share
+
+    /// LP tokens minted for a deposit, at the pool's current share price
+    pub fn shares_for_deposit(&self, amount: u64) -> Result<u64> {
+        if self.total_shares == 0 || self.collateral == 0 {
+            return Ok(amount);
+        }
+        Ok((amount as u128)
+            .checked_mul(self.total_shares as u128)
+            .ok_or(PredictionPumpError::MathOverflow)?
+            .checked_div(self.collateral as u128)
+            .ok_or(PredictionPumpError::MathOverflow)? as u64)
+    }
+
+    /// Collateral owed for burning `shares` LP tokens
+    pub fn collateral_for_shares(&self, shares: u64) -> Result<u64> {
+        require!(shares <= self.total_shares, PredictionPumpError::InsufficientLiquidity);
+        Ok((shares as u128)
+            .checked_mul(self.collateral as u128)
+            .ok_or(PredictionPumpError::MathOverflow)?
+            .checked_div(self.total_shares as u128)
+            .ok_or(PredictionPumpError::MathOverflow)? as u64)
+    }
+
+    /// Spread an LP fee over outstanding shares; returns false when there are
+    /// no LPs to credit
+    pub fn distribute_fee(&mut self, lp_fee: u64) -> Result<bool> {
+        if self.total_shares == 0 {
+            return Ok(false);
+        }
+        let increment = (lp_fee as u128)
+            .checked_mul(FEE_PER_SHARE_PRECISION)
+            .ok_or(PredictionPumpError::MathOverflow)?
+            .checked_div(self.total_shares as u128)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        self.fee_per_share = self
+            .fee_per_share
+            .checked_add(increment)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        Ok(true)
+    }
+
+    pub fn deposit(&mut self, amount: u64, shares: u64) -> Result<()> {
+        self.collateral = self
+            .collateral
+            .checked_add(amount)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        self.total_shares = self
+            .total_shares
+            .checked_add(shares)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn withdraw(&mut self, amount: u64, shares: u64) -> Result<()> {
+        self.collateral = self
+            .collateral
+            .checked_sub(amount)
+            .ok_or(PredictionPumpError::InsufficientLiquidity)?;
+        self.total_shares = self
+            .total_shares
+            .checked_sub(shares)
+            .ok_or(PredictionPumpError::InsufficientLiquidity)?;
+        Ok(())
+    }
+}
+
+impl LpPosition {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // market
+        32 + // owner
+        8 + // shares
+        16 + // reward_debt
+        8 + // fees_owed
+        8 + // unlocks_at
+        1; // bump
+
+    /// Start a new lockup after a deposit, ending early when trading closes so
+    /// providers are never locked past the point fees stop accruing
+    pub fn lock_at(&mut self, current_time: i64, resolution_date: i64) {
+        self.unlocks_at = current_time.saturating_add(LP_LOCKUP).min(resolution_date);
+    }
+
+    pub fn require_unlocked_at(&self, current_time: i64) -> Result<()> {
+        require!(current_time >= self.unlocks_at, PredictionPumpError::LiquidityLocked);
+        Ok(())
+    }
+
+    /// Move fees earned since the last checkpoint into `fees_owed`
+    pub fn settle(&mut self, fee_per_share: u128) -> Result<()> {
+        let earned = accumulated_rewards(self.shares, fee_per_share)?
+            .checked_sub(self.reward_debt)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        self.fees_owed = self
+            .fees_owed
+            .checked_add(earned as u64)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        self.reward_debt = accumulated_rewards(self.shares, fee_per_share)?;
+        Ok(())
+    }
+
+    pub fn deposit(&mut self, shares: u64, fee_per_share: u128) -> Result<()> {
+        self.settle(fee_per_share)?;
+        self.shares = self
+            .shares
+            .checked_add(shares)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        self.reward_debt = accumulated_rewards(self.shares, fee_per_share)?;
+        Ok(())
+    }
+
+    /// Remove shares from the position, returning every fee it has accrued
+    pub fn withdraw(&mut self, shares: u64, fee_per_share: u128) -> Result<u64> {
+        require!(shares <= self.shares, PredictionPumpError::InsufficientLiquidity);
+        self.settle(fee_per_share)?;
+        self.shares -= shares;
+        self.reward_debt = accumulated_rewards(self.shares, fee_per_share)?;
+        Ok(std::mem::take(&mut self.fees_owed))
+    }
+}
+
+impl CreatorRewards {
+    pub const LEN: usize = 8 + // total_staked
+        16 + // reward_per_token
+        8; // undistributed
+
+    /// Spread creator fees over the staked shares; fees arriving while nothing
+    /// is staked are kept for the market creator
+    pub fn distribute(&mut self, creator_fee: u64) -> Result<()> {
+        if self.total_staked == 0 {
+            self.undistributed = self
+                .undistributed
+                .checked_add(creator_fee)
+                .ok_or(PredictionPumpError::MathOverflow)?;
+            return Ok(());
+        }
+
+        let increment = (creator_fee as u128)
+            .checked_mul(FEE_PER_SHARE_PRECISION)
+            .ok_or(PredictionPumpError::MathOverflow)?
+            .checked_div(self.total_staked as u128)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        self.reward_per_token = self
+            .reward_per_token
+            .checked_add(increment)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn stake(&mut self, amount: u64) -> Result<()> {
+        self.total_staked = self
+            .total_staked
+            .checked_add(amount)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Take the fees that arrived while nothing was staked
+    pub fn take_undistributed(&mut self) -> Result<u64> {
+        let amount = self.undistributed;
+        require!(amount > 0, PredictionPumpError::NoFeesToClaim);
+        self.undistributed = 0;
+        Ok(amount)
+    }
+
+    pub fn unstake(&mut self, amount: u64) -> Result<()> {
+        self.total_staked = self
+            .total_staked
+            .checked_sub(amount)
+            .ok_or(PredictionPumpError::InsufficientStake)?;
+        Ok(())
+    }
+}
 
-        Ok(Market {
-            creator,
-            description,
-            resolution_date,
-            oracle_source,
-            outcome_tokens,
-            bonding_curve_params,
-            total_volume: 0,
-            status: MarketStatus {
-                is_active: false,
-                is_settled: false,
-                winning_outcome: None,
-                settlement_timestamp: None,
-            },
-            settlement_data: None,
-        })
+impl CreatorStake {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // market
+        32 + // owner
+        8 + // staked
+        16 + // reward_debt
+        8 + // rewards_owed
+        1; // bump
+
+    /// Move rewards earned since the last checkpoint into `rewards_owed`
+    pub fn settle(&mut self, reward_per_token: u128) -> Result<()> {
+        let accumulated = accumulated_rewards(self.staked, reward_per_token)?;
+        let earned = accumulated
+            .checked_sub(self.reward_debt)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        self.rewards_owed = self
+            .rewards_owed
+            .checked_add(earned as u64)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        self.reward_debt = accumulated;
+        Ok(())
+    }
+
+    pub fn deposit(&mut self, amount: u64, reward_per_token: u128) -> Result<()> {
+        self.settle(reward_per_token)?;
+        self.staked = self
+            .staked
+            .checked_add(amount)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        self.reward_debt = accumulated_rewards(self.staked, reward_per_token)?;
+        Ok(())
     }
+
+    pub fn withdraw(&mut self, amount: u64, reward_per_token: u128) -> Result<()> {
+        require!(amount <= self.staked, PredictionPumpError::InsufficientStake);
+        self.settle(reward_per_token)?;
+        self.staked -= amount;
+        self.reward_debt = accumulated_rewards(self.staked, reward_per_token)?;
+        Ok(())
+    }
+
+    /// Take every reward owed to the stake
+    pub fn claim(&mut self, reward_per_token: u128) -> Result<u64> {
+        self.settle(reward_per_token)?;
+        let amount = std::mem::take(&mut self.rewards_owed);
+        require!(amount > 0, PredictionPumpError::NoFeesToClaim);
+        Ok(amount)
+    }
+}
+
+impl MarketVault {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // market
+        1; // bump
 }
 
 impl MarketStatus {
     pub const LEN: usize = 1 + // is_active
         1 + // is_settled
+        1 + // is_disputed
+        1 + // is_voided
         1 + 1 + // winning_outcome (Option<u8>)
         1 + 8; // settlement_timestamp (Option<i64>)
+
+    /// Whether the creator bond can be released now: `true` to refund it after a
+    /// clean settlement and its lockup, `false` to slash it from a voided market
+    pub fn creator_bond_refund_at(&self, current_time: i64) -> Result<bool> {
+        require!(self.is_settled, PredictionPumpError::MarketNotSettled);
+        require!(!self.is_disputed, PredictionPumpError::MarketDisputed);
+        if self.is_voided {
+            return Ok(false);
+        }
+
+        let settlement_timestamp = self
+            .settlement_timestamp
+            .ok_or(PredictionPumpError::MarketNotSettled)?;
+        require!(
+            current_time >= settlement_timestamp.saturating_add(CREATOR_BOND_LOCKUP),
+            PredictionPumpError::CreatorBondLocked
+        );
+        Ok(true)
+    }
+
+    /// Check the outcome can still be disputed: any time before settlement and
+    /// for `DISPUTE_WINDOW` after it. A voided market is final
+    pub fn check_dispute_window_at(&self, current_time: i64) -> Result<()> {
+        require!(!self.is_voided, PredictionPumpError::DisputeWindowClosed);
+        if let Some(settlement_timestamp) = self.settlement_timestamp {
+            require!(
+                current_time < settlement_timestamp.saturating_add(DISPUTE_WINDOW),
+                PredictionPumpError::DisputeWindowClosed
+            );
+        }
+        Ok(())
+    }
 }
 
 impl BondingCurveParams {
@@ -396,11 +2477,17 @@ impl BondingCurveParams {
         8 + // max_supply
         2; // fee_rate
 
-    pub fn new(initial_price: u64, curve_steepness: u64, max_supply: u64, fee_rate: u16) -> Result<Self> {
+    pub fn new(
+        initial_price: u64,
+        curve_steepness: u64,
+        max_supply: u64,
+        fee_rate: u16,
+        fee_bounds: &FeeBounds,
+    ) -> Result<Self> {
         require!(initial_price > 0, PredictionPumpError::InvalidPrice);
         require!(curve_steepness > 0, PredictionPumpError::InvalidCurveParams);
         require!(max_supply > 0, PredictionPumpError::InvalidMaxSupply);
-        require!(fee_rate <= 1000, PredictionPumpError::FeeTooHigh); // Max 10%
+        fee_bounds.validate_fee_rate(fee_rate)?;
 
         Ok(BondingCurveParams {
             initial_price,
@@ -411,11 +2498,157 @@ impl BondingCurveParams {
     }
 }
 
+impl DynamicFee {
+    pub const LEN: usize = 2 + // min_fee_rate
+        2 + // max_fee_rate
+        8 + // target_volume
+        8 + // half_life
+        8 + // volume_ema
+        8; // last_trade
+
+    pub fn new(params: DynamicFeeParams, fee_bounds: &FeeBounds) -> Result<Self> {
+        require!(params.min_fee_rate <= params.max_fee_rate, PredictionPumpError::InvalidDynamicFee);
+        require!(params.target_volume > 0 && params.half_life > 0, PredictionPumpError::InvalidDynamicFee);
+        fee_bounds.validate_fee_rate(params.min_fee_rate)?;
+        fee_bounds.validate_fee_rate(params.max_fee_rate)?;
+
+        Ok(DynamicFee {
+            min_fee_rate: params.min_fee_rate,
+            max_fee_rate: params.max_fee_rate,
+            target_volume: params.target_volume,
+            half_life: params.half_life,
+            volume_ema: 0,
+            last_trade: 0,
+        })
+    }
+
+    /// Volume EMA decayed to `current_time`: halved once per elapsed half-life,
+    /// with linear interpolation inside the last one
+    pub fn volume_ema_at(&self, current_time: i64) -> u64 {
+        let elapsed = current_time.saturating_sub(self.last_trade).max(0) as u64;
+        let half_life = self.half_life as u64;
+        let halvings = elapsed / half_life;
+        if halvings >= 64 {
+            return 0;
+        }
+
+        let ema = self.volume_ema >> halvings;
+        let remainder = (elapsed % half_life) as u128;
+        let decay = (ema as u128) * remainder / (2 * half_life as u128);
+        ema - decay as u64
+    }
+
+    /// Fee rate rises linearly from min_fee_rate with no recent volume to
+    /// max_fee_rate once the EMA reaches target_volume
+    pub fn fee_rate_at(&self, current_time: i64) -> u16 {
+        let volume = self.volume_ema_at(current_time).min(self.target_volume);
+        let range = (self.max_fee_rate - self.min_fee_rate) as u128;
+        let increase = range * volume as u128 / self.target_volume as u128;
+        self.min_fee_rate + increase as u16
+    }
+
+    pub fn record_volume_at(&mut self, volume: u64, current_time: i64) -> Result<()> {
+        self.volume_ema = self
+            .volume_ema_at(current_time)
+            .checked_add(volume)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        self.last_trade = current_time;
+        Ok(())
+    }
+}
+
 impl SettlementData {
     pub const LEN: usize = 1 + // winning_outcome
         8 + // settlement_timestamp
         32 + // oracle_data_hash
-        8; // total_payout
+        8 + // total_payout
+        8 + // winning_supply
+        8; // total_claimed
+
+    /// Payout owed for `token_balance` winning tokens, pro rata against the
+    /// winning supply snapshot so burns by earlier claimers do not change shares
+    pub fn payout_for(&self, token_balance: u64) -> Result<u64> {
+        require!(self.winning_supply > 0, PredictionPumpError::NoWinningTokenSupply);
+
+        Ok((self.total_payout as u128)
+            .checked_mul(token_balance as u128)
+            .ok_or(PredictionPumpError::MathOverflow)?
+            .checked_div(self.winning_supply as u128)
+            .ok_or(PredictionPumpError::MathOverflow)? as u64)
+    }
+
+    pub fn unclaimed_payout(&self) -> Result<u64> {
+        self.total_payout
+            .checked_sub(self.total_claimed)
+            .ok_or(PredictionPumpError::MathOverflow.into())
+    }
+
+    /// Switch to a new winning outcome. Claims already paid under the old outcome
+    /// are final, so only the unclaimed pool is split among the new winners; the
+    /// winning supply is snapshotted again at the next claim
+    pub fn override_outcome(&mut self, winning_outcome: u8, oracle_data_hash: [u8; 32]) -> Result<()> {
+        self.total_payout = self.unclaimed_payout()?;
+        self.total_claimed = 0;
+        self.winning_supply = 0;
+        self.winning_outcome = winning_outcome;
+        self.oracle_data_hash = oracle_data_hash;
+        Ok(())
+    }
+}
+
+impl ResolutionCondition {
+    pub const LEN: usize = 1 + // comparator
+        16 + 4 + // threshold (mantissa + scale)
+        8 + // max_staleness
+        4; // min_oracle_results
+
+    /// Largest decimal scale accepted (matches Switchboard's own limit)
+    pub const MAX_SCALE: u32 = 28;
+
+    pub fn new(
+        comparator: Comparator,
+        threshold: SwitchboardDecimal,
+        max_staleness: i64,
+        min_oracle_results: u32,
+    ) -> Result<Self> {
+        require!(threshold.scale <= Self::MAX_SCALE, PredictionPumpError::InvalidResolutionCondition);
+        require!(max_staleness > 0, PredictionPumpError::InvalidResolutionCondition);
+        require!(min_oracle_results > 0, PredictionPumpError::InvalidResolutionCondition);
+
+        Ok(ResolutionCondition {
+            comparator,
+            threshold,
+            max_staleness,
+            min_oracle_results,
+        })
+    }
+
+    /// Return the winning outcome index for an oracle result
+    pub fn evaluate(&self, result: &SwitchboardDecimal) -> Result<u8> {
+        require!(result.scale <= Self::MAX_SCALE, PredictionPumpError::InvalidResolutionCondition);
+
+        // Rescale both values to the larger scale before comparing
+        let scale = result.scale.max(self.threshold.scale);
+        let value = Self::rescale(result, scale)?;
+        let threshold = Self::rescale(&self.threshold, scale)?;
+
+        let holds = match self.comparator {
+            Comparator::GreaterThan => value > threshold,
+            Comparator::GreaterThanOrEqual => value >= threshold,
+            Comparator::LessThan => value < threshold,
+            Comparator::LessThanOrEqual => value <= threshold,
+            Comparator::Equal => value == threshold,
+        };
+
+        Ok(if holds { 0 } else { 1 })
+    }
+
+    fn rescale(decimal: &SwitchboardDecimal, scale: u32) -> Result<i128> {
+        10i128
+            .checked_pow(scale - decimal.scale)
+            .and_then(|factor| decimal.mantissa.checked_mul(factor))
+            .ok_or(PredictionPumpError::MathOverflow.into())
+    }
 }
 
 // Oracle implementations - minimal for task 5.1
@@ -449,12 +2682,24 @@ impl OracleRegistry {
         self.oracles.iter().filter(|o| o.is_active).collect()
     }
 
+    pub fn is_active_oracle(&self, provider_id: &Pubkey) -> bool {
+        self.oracles.iter().any(|o| o.is_active && o.provider_id == *provider_id)
+    }
+
     pub fn select_fallback_oracle(&self, excluded_oracle: Pubkey) -> Option<&OracleProvider> {
         self.oracles
             .iter()
             .filter(|o| o.is_active && o.provider_id != excluded_oracle)
             .max_by_key(|o| o.reliability_score)
     }
+
+    /// Update a provider's reliability after a dispute on one of its reports.
+    /// Returns the updated provider, or None if the reporter is not registered.
+    pub fn record_dispute_outcome(&mut self, provider_id: Pubkey, upheld: bool) -> Option<&OracleProvider> {
+        let provider = self.oracles.iter_mut().find(|o| o.provider_id == provider_id)?;
+        provider.record_dispute_outcome(upheld);
+        Some(provider)
+    }
 }
 
 impl OracleProvider {
@@ -482,6 +2727,20 @@ impl OracleProvider {
         self.is_active = false;
         Ok(())
     }
+
+    /// Raise the score for an upheld report, lower it for an overridden one,
+    /// and deactivate the provider once it drops below the minimum score
+    pub fn record_dispute_outcome(&mut self, upheld: bool) {
+        if upheld {
+            self.reliability_score = self.reliability_score.saturating_add(RELIABILITY_REWARD).min(10000);
+        } else {
+            self.reliability_score = self.reliability_score.saturating_sub(RELIABILITY_PENALTY);
+        }
+
+        if self.reliability_score < MIN_RELIABILITY_SCORE {
+            self.is_active = false;
+        }
+    }
 }
 
 impl OracleData {
@@ -491,57 +2750,151 @@ impl OracleData {
         1 + // winning_outcome
         2 + // confidence_score
         8 + // timestamp
+        4 + MAX_ORACLE_EVIDENCE_LEN + // evidence
         32 + // data_hash
-        1; // is_disputed
+        1 + // is_disputed
+        1; // is_ruled
+
+    /// Domain separator prefixed to every oracle report message
+    pub const REPORT_DOMAIN: &'static [u8] = b"prediction-pump:oracle-report:v1";
 
     pub fn new(
         market: Pubkey,
         oracle_provider: Pubkey,
         winning_outcome: u8,
         confidence_score: u16,
+        evidence: Vec<u8>,
+    ) -> Result<Self> {
+        Self::from_report(
+            market,
+            oracle_provider,
+            winning_outcome,
+            confidence_score,
+            Clock::get()?.unix_timestamp,
+            evidence,
+        )
+    }
+
+    /// Build oracle data for a report made at `timestamp`
+    pub fn from_report(
+        market: Pubkey,
+        oracle_provider: Pubkey,
+        winning_outcome: u8,
+        confidence_score: u16,
+        timestamp: i64,
+        evidence: Vec<u8>,
     ) -> Result<Self> {
         require!(confidence_score <= 10000, PredictionPumpError::InvalidConfidenceScore);
+        require!(evidence.len() <= MAX_ORACLE_EVIDENCE_LEN, PredictionPumpError::EvidenceTooLong);
 
         // Generate data hash for integrity verification
-        let mut hasher = solana_program::hash::Hasher::default();
-        hasher.hash(market.as_ref());
-        hasher.hash(oracle_provider.as_ref());
-        hasher.hash(&winning_outcome.to_le_bytes());
-        hasher.hash(&confidence_score.to_le_bytes());
-        let data_hash = hasher.result().to_bytes();
+        let message = Self::report_message(
+            &market,
+            &oracle_provider,
+            winning_outcome,
+            confidence_score,
+            timestamp,
+            &evidence,
+        );
+        let data_hash = solana_program::hash::hash(&message).to_bytes();
 
         Ok(OracleData {
             market,
             oracle_provider,
             winning_outcome,
             confidence_score,
-            timestamp: Clock::get()?.unix_timestamp,
+            timestamp,
+            evidence,
             data_hash,
             is_disputed: false,
+            is_ruled: false,
         })
     }
 
+    /// Canonical encoding of a report; this is what `data_hash` covers and
+    /// what off-chain oracles sign with their ed25519 key
+    pub fn report_message(
+        market: &Pubkey,
+        oracle_provider: &Pubkey,
+        winning_outcome: u8,
+        confidence_score: u16,
+        timestamp: i64,
+        evidence: &[u8],
+    ) -> Vec<u8> {
+        let mut message = Vec::with_capacity(Self::REPORT_DOMAIN.len() + 32 + 32 + 1 + 2 + 8 + 2 + evidence.len());
+        message.extend_from_slice(Self::REPORT_DOMAIN);
+        message.extend_from_slice(market.as_ref());
+        message.extend_from_slice(oracle_provider.as_ref());
+        message.extend_from_slice(&winning_outcome.to_le_bytes());
+        message.extend_from_slice(&confidence_score.to_le_bytes());
+        message.extend_from_slice(&timestamp.to_le_bytes());
+        message.extend_from_slice(&(evidence.len() as u16).to_le_bytes());
+        message.extend_from_slice(evidence);
+        message
+    }
+
+    /// Report message for this oracle data
+    pub fn message(&self) -> Vec<u8> {
+        Self::report_message(
+            &self.market,
+            &self.oracle_provider,
+            self.winning_outcome,
+            self.confidence_score,
+            self.timestamp,
+            &self.evidence,
+        )
+    }
+
     pub fn validate_data_integrity(&self) -> Result<bool> {
         // Recreate hash and compare
-        let mut hasher = solana_program::hash::Hasher::default();
-        hasher.hash(self.market.as_ref());
-        hasher.hash(self.oracle_provider.as_ref());
-        hasher.hash(&self.winning_outcome.to_le_bytes());
-        hasher.hash(&self.confidence_score.to_le_bytes());
-        let computed_hash = hasher.result().to_bytes();
-        
+        let computed_hash = solana_program::hash::hash(&self.message()).to_bytes();
+
         Ok(computed_hash == self.data_hash)
     }
 
     pub fn dispute(&mut self) -> Result<()> {
         require!(!self.is_disputed, PredictionPumpError::AlreadyDisputed);
+        require!(!self.is_ruled, PredictionPumpError::DisputeAlreadyFinalized);
         self.is_disputed = true;
         Ok(())
     }
+
+    /// Replace the outcome with a dispute ruling. The hash is recomputed so the
+    /// corrected record passes integrity checks at settlement
+    pub fn override_outcome(&mut self, winning_outcome: u8) {
+        self.winning_outcome = winning_outcome;
+        self.data_hash = solana_program::hash::hash(&self.message()).to_bytes();
+        self.is_disputed = false;
+    }
 }
 
 // Dispute resolution structures
 
+/// Global dispute parameters, stored at `["dispute_config"]`
+#[account]
+pub struct DisputeConfig {
+    pub authority: Pubkey,
+    pub voting_period: i64, // commit phase length in seconds
+    pub reveal_period: i64,
+    pub appeal_period: i64, // time after a round is resolved to appeal it
+    pub min_stake: u64, // minimum dispute stake in lamports
+    pub quorum: u64, // minimum revealed vote weight for a binding result
+    pub supermajority_bps: u16, // share of revealed votes needed to override
+    pub max_appeals: u8,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct DisputeConfigParams {
+    pub voting_period: i64,
+    pub reveal_period: i64,
+    pub appeal_period: i64,
+    pub min_stake: u64,
+    pub quorum: u64,
+    pub supermajority_bps: u16,
+    pub max_appeals: u8,
+}
+
 #[account]
 pub struct Dispute {
     pub market: Pubkey,
@@ -551,22 +2904,110 @@ pub struct Dispute {
     pub stake_amount: u64,
     pub submission_time: i64,
     pub voting_end_time: i64,
-    pub votes: Vec<DisputeVote>,
-    pub is_resolved: bool,
+    pub reveal_end_time: i64,
+    pub quorum: u64, // copied from DisputeConfig when the dispute opens
+    pub supermajority_bps: u16,
+    pub voting_period: i64,
+    pub reveal_period: i64,
+    pub appeal_period: i64,
+    pub max_appeals: u8,
+    pub round: u8, // 0 for the first vote, incremented by each appeal
+    pub round_stake: u64, // stake that opened the current round
+    pub appeal_deadline: i64, // set when the current round is resolved
+    pub past_rounds: Vec<DisputeRound>, // appealed rounds, kept for voter payouts
+    pub oracle_bond: u64, // forfeited oracle bond added to the final reward pool
+    pub bond_pending: bool, // an oracle bond still has to be settled before rewards are claimable
+    pub evidence_count: u16, // DisputeEvidence records attached so far
+    pub voter_count: u32,
+    pub committed_votes: u64, // total weight committed
+    pub outcome_0_votes: u64, // revealed weight per outcome
+    pub outcome_1_votes: u64,
+    pub uphold_votes: u64,
+    pub void_votes: u64,
+    pub is_resolved: bool, // current round resolved
+    pub is_finalized: bool, // ruling binding, no further appeals
     pub resolution: Option<DisputeResolution>,
 }
 
+/// Ruling of a round that was appealed
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct DisputeRound {
+    pub stake_amount: u64,
+    pub committed_votes: u64,
+    pub resolution: DisputeResolution,
+}
+
+/// Marks a dispute reward as claimed, stored at `["reward_receipt", dispute, claimant, round]`
+#[account]
+pub struct RewardReceipt {
+    pub dispute: Pubkey,
+    pub claimant: Pubkey,
+    pub round: u8, // DISPUTER_RECEIPT_ROUND for the disputer's claim
+    pub amount: u64,
+    pub bump: u8,
+}
+
+/// Evidence attached to a dispute, stored at `["evidence", dispute, index]`
+#[account]
+#[derive(Debug, PartialEq)]
+pub struct DisputeEvidence {
+    pub dispute: Pubkey,
+    pub submitter: Pubkey,
+    pub index: u16,
+    pub round: u8, // voting round the evidence was submitted in
+    pub content_hash: [u8; 32], // hash of the off-chain evidence document
+    pub uri: String,
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+/// Program-owned escrow holding the stake that opened an appeal round
+#[account]
+pub struct AppealEscrow {
+    pub dispute: Pubkey,
+    pub appellant: Pubkey,
+    pub round: u8,
+    pub amount: u64,
+    pub appealed_outcome: DisputeOutcome, // ruling the appellant challenged
+    pub bump: u8,
+}
+
+/// Program-owned escrow holding a dispute's stake
+#[account]
+pub struct DisputeEscrow {
+    pub dispute: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+/// Program-owned escrow holding the stake locked by a dispute's voters
+#[account]
+pub struct VoteEscrow {
+    pub dispute: Pubkey,
+    pub total_locked: u64,
+    pub bump: u8,
+}
+
+/// A voter's vote in one round of a dispute, stored at `["vote", dispute, voter, round]`
+#[account]
+#[derive(Debug, PartialEq)]
 pub struct DisputeVote {
+    pub dispute: Pubkey,
     pub voter: Pubkey,
-    pub outcome: u8,
-    pub weight: u64,
+    pub round: u8,
+    pub commitment: [u8; 32], // hash(outcome, salt, voter)
+    pub outcome: Option<u8>,  // set once revealed
+    pub weight: u64, // lamports locked in the vote escrow
     pub timestamp: i64,
+    pub stake_withdrawn: bool,
+    pub bump: u8,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
 pub struct DisputeResolution {
     pub outcome: DisputeOutcome,
+    pub quorum_reached: bool,
+    pub decisive: bool, // quorum met and the ruling won its vote rather than being the fallback
     pub total_votes: u64,
     pub winning_votes: u64,
     pub resolution_timestamp: i64,
@@ -576,6 +3017,47 @@ pub struct DisputeResolution {
 pub enum DisputeOutcome {
     UpholdOriginal,
     OverrideOutcome(u8),
+    VoidMarket, // the market itself is malformed; refund every outcome token
+}
+
+impl DisputeConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        8 + // voting_period
+        8 + // reveal_period
+        8 + // appeal_period
+        8 + // min_stake
+        8 + // quorum
+        2 + // supermajority_bps
+        1 + // max_appeals
+        1; // bump
+
+    pub fn new(authority: Pubkey, params: DisputeConfigParams, bump: u8) -> Result<Self> {
+        require!(params.voting_period > 0, PredictionPumpError::InvalidDisputeConfig);
+        require!(params.reveal_period > 0, PredictionPumpError::InvalidDisputeConfig);
+        require!(params.appeal_period > 0, PredictionPumpError::InvalidDisputeConfig);
+        require!(params.min_stake > 0, PredictionPumpError::InvalidDisputeConfig);
+        require!(
+            (5000..=10000).contains(&params.supermajority_bps),
+            PredictionPumpError::InvalidDisputeConfig
+        );
+        require!(
+            params.max_appeals <= MAX_DISPUTE_APPEALS,
+            PredictionPumpError::InvalidDisputeConfig
+        );
+
+        Ok(DisputeConfig {
+            authority,
+            voting_period: params.voting_period,
+            reveal_period: params.reveal_period,
+            appeal_period: params.appeal_period,
+            min_stake: params.min_stake,
+            quorum: params.quorum,
+            supermajority_bps: params.supermajority_bps,
+            max_appeals: params.max_appeals,
+            bump,
+        })
+    }
 }
 
 impl Dispute {
@@ -583,12 +3065,27 @@ impl Dispute {
         32 + // market
         32 + // oracle_data
         32 + // disputer
-        4 + 200 + // reason (max 200 chars)
+        4 + MAX_DISPUTE_REASON_LEN + // reason
         8 + // stake_amount
         8 + // submission_time
         8 + // voting_end_time
-        4 + (DisputeVote::LEN * 100) + // votes (max 100 votes)
+        8 + // reveal_end_time
+        8 + // quorum
+        2 + // supermajority_bps
+        8 + 8 + 8 + // voting_period, reveal_period, appeal_period
+        1 + // max_appeals
+        1 + // round
+        8 + // round_stake
+        8 + // appeal_deadline
+        4 + (DisputeRound::LEN * MAX_DISPUTE_APPEALS as usize) + // past_rounds
+        8 + // oracle_bond
+        1 + // bond_pending
+        2 + // evidence_count
+        4 + // voter_count
+        8 + // committed_votes
+        8 + 8 + 8 + 8 + // outcome_0_votes, outcome_1_votes, uphold_votes, void_votes
         1 + // is_resolved
+        1 + // is_finalized
         1 + DisputeResolution::LEN; // resolution (Option)
 
     pub fn new(
@@ -597,9 +3094,10 @@ impl Dispute {
         disputer: Pubkey,
         reason: String,
         stake_amount: u64,
+        config: &DisputeConfig,
     ) -> Result<Self> {
         let current_time = Clock::get()?.unix_timestamp;
-        let voting_period = 7 * 24 * 60 * 60; // 7 days in seconds
+        let voting_end_time = current_time + config.voting_period;
 
         Ok(Dispute {
             market,
@@ -608,94 +3106,500 @@ impl Dispute {
             reason,
             stake_amount,
             submission_time: current_time,
-            voting_end_time: current_time + voting_period,
-            votes: Vec::new(),
+            voting_end_time,
+            reveal_end_time: voting_end_time + config.reveal_period,
+            quorum: config.quorum,
+            supermajority_bps: config.supermajority_bps,
+            voting_period: config.voting_period,
+            reveal_period: config.reveal_period,
+            appeal_period: config.appeal_period,
+            max_appeals: config.max_appeals,
+            round: 0,
+            round_stake: stake_amount,
+            appeal_deadline: 0,
+            past_rounds: Vec::new(),
+            oracle_bond: 0,
+            bond_pending: false,
+            evidence_count: 0,
+            voter_count: 0,
+            committed_votes: 0,
+            outcome_0_votes: 0,
+            outcome_1_votes: 0,
+            uphold_votes: 0,
+            void_votes: 0,
             is_resolved: false,
+            is_finalized: false,
             resolution: None,
         })
     }
 
-    pub fn add_vote(&mut self, vote: DisputeVote) -> Result<()> {
-        require!(self.votes.len() < 100, PredictionPumpError::TooManyVotes);
-        require!(!self.votes.iter().any(|v| v.voter == vote.voter), PredictionPumpError::AlreadyVoted);
-        
-        self.votes.push(vote);
+    /// Reserve the next evidence index; evidence is only accepted while votes are open
+    pub fn record_evidence_at(&mut self, current_time: i64) -> Result<u16> {
+        require!(!self.is_resolved, PredictionPumpError::DisputeAlreadyResolved);
+        require!(current_time < self.voting_end_time, PredictionPumpError::VotingPeriodEnded);
+        require!(
+            self.evidence_count < MAX_DISPUTE_EVIDENCE,
+            PredictionPumpError::EvidenceLimitReached
+        );
+
+        let index = self.evidence_count;
+        self.evidence_count += 1;
+        Ok(index)
+    }
+
+    /// Add a committed vote's weight to the running tallies
+    pub fn record_commit(&mut self, weight: u64) -> Result<()> {
+        self.voter_count = self
+            .voter_count
+            .checked_add(1)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        self.committed_votes = self
+            .committed_votes
+            .checked_add(weight)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Add a revealed vote's weight to its outcome's tally
+    pub fn record_reveal(&mut self, outcome: u8, weight: u64) -> Result<()> {
+        let tally = match outcome {
+            0 => &mut self.outcome_0_votes,
+            1 => &mut self.outcome_1_votes,
+            UPHOLD_ORIGINAL_VOTE => &mut self.uphold_votes,
+            VOID_MARKET_VOTE => &mut self.void_votes,
+            _ => return err!(PredictionPumpError::InvalidVoteOutcome),
+        };
+        *tally = tally.checked_add(weight).ok_or(PredictionPumpError::MathOverflow)?;
         Ok(())
     }
 
+    /// Total weight of revealed votes
+    pub fn revealed_votes(&self) -> Result<u64> {
+        self.outcome_0_votes
+            .checked_add(self.outcome_1_votes)
+            .and_then(|v| v.checked_add(self.uphold_votes))
+            .and_then(|v| v.checked_add(self.void_votes))
+            .ok_or(PredictionPumpError::MathOverflow.into())
+    }
+
     pub fn calculate_resolution(&self) -> Result<DisputeResolution> {
-        require!(!self.votes.is_empty(), PredictionPumpError::NoVotes);
-
-        // Count votes by outcome (simple approach for binary outcomes + uphold option)
-        let mut outcome_0_votes = 0u64;
-        let mut outcome_1_votes = 0u64;
-        let mut uphold_votes = 0u64;
-        let mut total_votes = 0u64;
-
-        for vote in &self.votes {
-            total_votes += vote.weight;
-            match vote.outcome {
-                0 => outcome_0_votes += vote.weight,
-                1 => outcome_1_votes += vote.weight,
-                255 => uphold_votes += vote.weight, // Special value for "uphold original"
-                _ => {} // Ignore invalid outcomes
-            }
+        self.calculate_resolution_at(Clock::get()?.unix_timestamp)
+    }
+
+    /// Resolve from the revealed tallies; unrevealed commitments do not count.
+    /// Without quorum, or without a supermajority for a new outcome or for voiding
+    /// the market, the original outcome is upheld
+    pub fn calculate_resolution_at(&self, resolution_timestamp: i64) -> Result<DisputeResolution> {
+        let outcome_0_votes = self.outcome_0_votes;
+        let outcome_1_votes = self.outcome_1_votes;
+        let uphold_votes = self.uphold_votes;
+        let total_votes = self.revealed_votes()?;
+
+        let quorum_reached = total_votes > 0 && total_votes >= self.quorum;
+        let uphold = DisputeResolution {
+            outcome: DisputeOutcome::UpholdOriginal,
+            quorum_reached,
+            decisive: false,
+            total_votes,
+            winning_votes: uphold_votes,
+            resolution_timestamp,
+        };
+        if !quorum_reached {
+            return Ok(uphold);
         }
 
-        // Find winning outcome
-        let (winning_outcome, winning_votes) = if uphold_votes >= outcome_0_votes && uphold_votes >= outcome_1_votes {
-            (255u8, uphold_votes)
-        } else if outcome_0_votes >= outcome_1_votes {
-            (0u8, outcome_0_votes)
+        // Find the leading alternative to the report; voiding must beat both outcomes
+        let (leading_outcome, leading_votes) = if outcome_0_votes >= outcome_1_votes {
+            (DisputeOutcome::OverrideOutcome(0), outcome_0_votes)
         } else {
-            (1u8, outcome_1_votes)
+            (DisputeOutcome::OverrideOutcome(1), outcome_1_votes)
         };
-
-        // Determine if original outcome should be upheld or overridden
-        let outcome = if winning_outcome == 255 {
-            DisputeOutcome::UpholdOriginal
+        let (leading_outcome, leading_votes) = if self.void_votes > leading_votes {
+            (DisputeOutcome::VoidMarket, self.void_votes)
         } else {
-            DisputeOutcome::OverrideOutcome(winning_outcome)
+            (leading_outcome, leading_votes)
         };
 
+        // Overriding needs a supermajority of the revealed votes
+        let required_votes = (total_votes as u128)
+            .checked_mul(self.supermajority_bps as u128)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        // The report stands on its own votes when no alternative leads it
+        if uphold_votes >= leading_votes {
+            return Ok(DisputeResolution { decisive: true, ..uphold });
+        }
+        if (leading_votes as u128) * 10000 < required_votes {
+            return Ok(uphold);
+        }
+
         Ok(DisputeResolution {
-            outcome,
+            outcome: leading_outcome,
+            quorum_reached,
+            decisive: true,
             total_votes,
-            winning_votes,
-            resolution_timestamp: Clock::get()?.unix_timestamp,
+            winning_votes: leading_votes,
+            resolution_timestamp,
         })
     }
 
+    /// Binary outcomes plus the "uphold original" and "void market" votes
+    pub fn is_valid_vote_outcome(outcome: u8) -> bool {
+        outcome <= 1 || outcome == UPHOLD_ORIGINAL_VOTE || outcome == VOID_MARKET_VOTE
+    }
+
+    /// Committed weight and ruling of a resolved round; each round's voters are
+    /// paid by that round's ruling
+    fn round_result(&self, round: u8) -> Result<(u64, &DisputeResolution)> {
+        if round == self.round {
+            let resolution = self
+                .resolution
+                .as_ref()
+                .ok_or(PredictionPumpError::DisputeNotResolved)?;
+            Ok((self.committed_votes, resolution))
+        } else {
+            let past_round = self
+                .past_rounds
+                .get(round as usize)
+                .ok_or(PredictionPumpError::VoteNotFound)?;
+            Ok((past_round.committed_votes, &past_round.resolution))
+        }
+    }
+
+    fn slash(weight: u64) -> Result<u64> {
+        Ok((weight as u128)
+            .checked_mul(VOTER_SLASH_BPS as u128)
+            .ok_or(PredictionPumpError::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(PredictionPumpError::MathOverflow)? as u64)
+    }
+
+    /// Stake returned to a voter once their round is resolved: winners get their
+    /// full stake, losers their stake minus the slash, unrevealed voters nothing.
+    /// When the report is only upheld as the fallback, for lack of quorum or of a
+    /// supermajority, there is no reward pool and every stake is returned in full
+    pub fn vote_principal(&self, vote: &DisputeVote) -> Result<u64> {
+        let (_, resolution) = self.round_result(vote.round)?;
+        if !resolution.decisive {
+            return Ok(vote.weight);
+        }
+
+        let Some(outcome) = vote.outcome else {
+            return Ok(0);
+        };
+
+        if resolution.is_winning_vote(outcome) {
+            return Ok(vote.weight);
+        }
+
+        vote.weight
+            .checked_sub(Self::slash(vote.weight)?)
+            .ok_or(PredictionPumpError::MathOverflow.into())
+    }
+
+    /// A round's reward pool and the winning weight it is shared across. The pool
+    /// holds the slashed losing stake and all forfeited unrevealed stake; when the
+    /// final ruling overturns the report it also holds the forfeited oracle bond and
+    /// the disputer shares it with weight equal to their stake
+    pub fn reward_pool(&self, round: u8) -> Result<(u64, u64)> {
+        let (committed_votes, resolution) = self.round_result(round)?;
+
+        if !resolution.decisive {
+            return Ok((0, 0));
+        }
+
+        let losing_votes = resolution
+            .total_votes
+            .checked_sub(resolution.winning_votes)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        let forfeited_votes = committed_votes
+            .checked_sub(resolution.total_votes)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        let mut pool = Self::slash(losing_votes)?
+            .checked_add(forfeited_votes)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        let mut winning_weight = resolution.winning_votes;
+
+        if round == self.round && resolution.outcome != DisputeOutcome::UpholdOriginal {
+            pool = pool
+                .checked_add(self.oracle_bond)
+                .ok_or(PredictionPumpError::MathOverflow)?;
+            winning_weight = winning_weight
+                .checked_add(self.stake_amount)
+                .ok_or(PredictionPumpError::MathOverflow)?;
+        }
+
+        Ok((pool, winning_weight))
+    }
+
+    fn pool_share(&self, round: u8, weight: u64) -> Result<u64> {
+        let (pool, winning_weight) = self.reward_pool(round)?;
+        if winning_weight == 0 {
+            return Ok(0);
+        }
+
+        Ok((pool as u128)
+            .checked_mul(weight as u128)
+            .ok_or(PredictionPumpError::MathOverflow)?
+            .checked_div(winning_weight as u128)
+            .ok_or(PredictionPumpError::MathOverflow)? as u64)
+    }
+
+    /// Winning voter's pro-rata share of their round's reward pool
+    pub fn vote_reward(&self, vote: &DisputeVote) -> Result<u64> {
+        let (_, resolution) = self.round_result(vote.round)?;
+
+        match vote.outcome {
+            Some(outcome) if resolution.decisive && resolution.is_winning_vote(outcome) => {
+                self.pool_share(vote.round, vote.weight)
+            }
+            _ => Ok(0),
+        }
+    }
+
+    /// Disputer's share of the final round's reward pool if the report was overturned
+    pub fn disputer_reward(&self) -> Result<u64> {
+        let (_, resolution) = self.round_result(self.round)?;
+
+        match resolution.outcome {
+            DisputeOutcome::OverrideOutcome(_) | DisputeOutcome::VoidMarket => {
+                self.pool_share(self.round, self.stake_amount)
+            }
+            DisputeOutcome::UpholdOriginal => Ok(0),
+        }
+    }
+
+    /// Record the current round's ruling and open its appeal window (none in the last round)
     pub fn resolve(&mut self, resolution: DisputeResolution) -> Result<()> {
         require!(!self.is_resolved, PredictionPumpError::DisputeAlreadyResolved);
         
+        self.appeal_deadline = if self.can_appeal() {
+            resolution
+                .resolution_timestamp
+                .checked_add(self.appeal_period)
+                .ok_or(PredictionPumpError::MathOverflow)?
+        } else {
+            resolution.resolution_timestamp
+        };
         self.is_resolved = true;
         self.resolution = Some(resolution);
         Ok(())
     }
+
+    pub fn can_appeal(&self) -> bool {
+        self.round < self.max_appeals
+    }
+
+    /// Each appeal doubles the stake of the round before it
+    pub fn appeal_stake(&self) -> Result<u64> {
+        self.round_stake
+            .checked_mul(2)
+            .ok_or(PredictionPumpError::MathOverflow.into())
+    }
+
+    /// Archive the current ruling and open a new voting round against it,
+    /// returning the stake the appellant must post
+    pub fn appeal_at(&mut self, current_time: i64) -> Result<u64> {
+        require!(self.is_resolved, PredictionPumpError::DisputeNotResolved);
+        require!(!self.is_finalized, PredictionPumpError::DisputeAlreadyFinalized);
+        require!(self.can_appeal(), PredictionPumpError::MaxAppealsReached);
+        require!(current_time < self.appeal_deadline, PredictionPumpError::AppealPeriodEnded);
+
+        let stake_amount = self.appeal_stake()?;
+        let resolution = self
+            .resolution
+            .take()
+            .ok_or(PredictionPumpError::DisputeNotResolved)?;
+        self.past_rounds.push(DisputeRound {
+            stake_amount: self.round_stake,
+            committed_votes: self.committed_votes,
+            resolution,
+        });
+
+        self.round += 1;
+        self.round_stake = stake_amount;
+        self.voting_end_time = current_time + self.voting_period;
+        self.reveal_end_time = self.voting_end_time + self.reveal_period;
+        self.appeal_deadline = 0;
+        self.voter_count = 0;
+        self.committed_votes = 0;
+        self.outcome_0_votes = 0;
+        self.outcome_1_votes = 0;
+        self.uphold_votes = 0;
+        self.void_votes = 0;
+        self.is_resolved = false;
+
+        Ok(stake_amount)
+    }
+
+    /// Make the current ruling binding once its appeal window has passed
+    pub fn finalize_at(&mut self, current_time: i64) -> Result<()> {
+        require!(self.is_resolved, PredictionPumpError::DisputeNotResolved);
+        require!(!self.is_finalized, PredictionPumpError::DisputeAlreadyFinalized);
+        require!(current_time >= self.appeal_deadline, PredictionPumpError::AppealPeriodNotEnded);
+
+        self.is_finalized = true;
+        Ok(())
+    }
+}
+
+impl DisputeEscrow {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // dispute
+        8 + // amount
+        1; // bump
+}
+
+impl DisputeRound {
+    pub const LEN: usize = 8 + // stake_amount
+        8 + // committed_votes
+        DisputeResolution::LEN; // resolution
+}
+
+impl RewardReceipt {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // dispute
+        32 + // claimant
+        1 + // round
+        8 + // amount
+        1; // bump
+}
+
+impl DisputeEvidence {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // dispute
+        32 + // submitter
+        2 + // index
+        1 + // round
+        32 + // content_hash
+        4 + MAX_EVIDENCE_URI_LEN + // uri
+        8 + // timestamp
+        1; // bump
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        dispute: Pubkey,
+        submitter: Pubkey,
+        index: u16,
+        round: u8,
+        content_hash: [u8; 32],
+        uri: String,
+        timestamp: i64,
+        bump: u8,
+    ) -> Result<Self> {
+        require!(
+            !uri.is_empty() && uri.len() <= MAX_EVIDENCE_URI_LEN,
+            PredictionPumpError::InvalidEvidenceUri
+        );
+
+        Ok(DisputeEvidence {
+            dispute,
+            submitter,
+            index,
+            round,
+            content_hash,
+            uri,
+            timestamp,
+            bump,
+        })
+    }
+}
+
+impl AppealEscrow {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // dispute
+        32 + // appellant
+        1 + // round
+        8 + // amount
+        1 + 1 + // appealed_outcome (enum discriminant + data)
+        1; // bump
+}
+
+impl VoteEscrow {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // dispute
+        8 + // total_locked
+        1; // bump
 }
 
 impl DisputeVote {
-    pub const LEN: usize = 32 + // voter
-        1 + // outcome
+    pub const LEN: usize = 8 + // discriminator
+        32 + // dispute
+        32 + // voter
+        1 + // round
+        32 + // commitment
+        1 + 1 + // outcome (Option<u8>)
         8 + // weight
-        8; // timestamp
+        8 + // timestamp
+        1 + // stake_withdrawn
+        1; // bump
 
-    pub fn new(voter: Pubkey, outcome: u8, weight: u64) -> Result<Self> {
+    pub fn new(dispute: Pubkey, voter: Pubkey, round: u8, commitment: [u8; 32], weight: u64, bump: u8) -> Result<Self> {
         Ok(DisputeVote {
+            dispute,
             voter,
-            outcome,
+            round,
+            commitment,
+            outcome: None,
             weight,
             timestamp: Clock::get()?.unix_timestamp,
+            stake_withdrawn: false,
+            bump,
         })
     }
+
+    /// Open the commitment; the outcome and salt must hash to it
+    pub fn reveal(&mut self, outcome: u8, salt: &[u8; 32]) -> Result<()> {
+        require!(self.outcome.is_none(), PredictionPumpError::VoteAlreadyRevealed);
+        require!(
+            Self::compute_commitment(outcome, salt, &self.voter) == self.commitment,
+            PredictionPumpError::CommitmentMismatch
+        );
+        require!(Dispute::is_valid_vote_outcome(outcome), PredictionPumpError::InvalidVoteOutcome);
+
+        self.outcome = Some(outcome);
+        Ok(())
+    }
+
+    /// Commitment a voter submits in the commit phase. Binding the voter's key
+    /// stops others from copying a commitment and revealing it as their own.
+    pub fn compute_commitment(outcome: u8, salt: &[u8; 32], voter: &Pubkey) -> [u8; 32] {
+        solana_program::hash::hashv(&[&[outcome], salt, voter.as_ref()]).to_bytes()
+    }
 }
 
 impl DisputeResolution {
     pub const LEN: usize = 1 + 1 + // outcome (enum discriminant + data)
+        1 + // quorum_reached
+        1 + // decisive
         8 + // total_votes
         8 + // winning_votes
         8; // resolution_timestamp
+
+    /// Whether a vote for `outcome` sided with this resolution
+    pub fn is_winning_vote(&self, outcome: u8) -> bool {
+        match self.outcome {
+            DisputeOutcome::UpholdOriginal => outcome == UPHOLD_ORIGINAL_VOTE,
+            DisputeOutcome::OverrideOutcome(winning_outcome) => outcome == winning_outcome,
+            DisputeOutcome::VoidMarket => outcome == VOID_MARKET_VOTE,
+        }
+    }
+}
+
+#[event]
+pub struct DisputeRewardClaimedEvent {
+    pub dispute: Pubkey,
+    pub claimant: Pubkey,
+    pub round: u8,
+    pub amount: u64,
+}
+
+#[event]
+pub struct EvidenceAddedEvent {
+    pub dispute: Pubkey,
+    pub evidence: Pubkey,
+    pub submitter: Pubkey,
+    pub index: u16,
+    pub content_hash: [u8; 32],
 }
 
 
@@ -710,7 +3614,7 @@ pub enum PredictionPumpError {
     TooManyOutcomes,
     #[msg("Resolution date must be in the future")]
     InvalidResolutionDate,
-    #[msg("Fee rate cannot exceed 10%")]
+    #[msg("Fee rate is outside the protocol's allowed bounds")]
     FeeTooHigh,
     #[msg("Invalid price (must be greater than 0)")]
     InvalidPrice,
@@ -784,4 +3688,117 @@ pub enum PredictionPumpError {
     TooManyVotes,
     #[msg("No votes submitted for dispute")]
     NoVotes,
+    // Price feed resolution errors
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Invalid resolution condition")]
+    InvalidResolutionCondition,
+    #[msg("Market has no resolution condition configured")]
+    NoResolutionCondition,
+    #[msg("Invalid Switchboard aggregator account")]
+    InvalidAggregatorAccount,
+    #[msg("Oracle result is stale")]
+    StaleOracleData,
+    #[msg("Oracle round has too few successful responses")]
+    InsufficientOracleResults,
+    // Optimistic resolution errors
+    #[msg("Market does not use optimistic resolution")]
+    OptimisticResolutionDisabled,
+    #[msg("Invalid optimistic resolution configuration")]
+    InvalidOptimisticConfig,
+    #[msg("Proposal liveness period has ended")]
+    LivenessPeriodEnded,
+    #[msg("Proposal liveness period has not ended yet")]
+    LivenessPeriodNotEnded,
+    #[msg("Proposal has already been finalized")]
+    ProposalAlreadyFinalized,
+    #[msg("Dispute has not been resolved yet")]
+    DisputeNotResolved,
+    #[msg("Bond recipient does not match the proposal outcome")]
+    InvalidBondRecipient,
+    // Oracle report errors
+    #[msg("Oracle evidence too long (max 64 bytes)")]
+    EvidenceTooLong,
+    #[msg("Invalid ed25519 signature instruction")]
+    InvalidSignatureInstruction,
+    #[msg("Signed report does not match the expected signer or message")]
+    SignatureMismatch,
+    #[msg("Oracle report timestamp is in the future")]
+    InvalidReportTimestamp,
+    // Vote stake errors
+    #[msg("Invalid vote outcome")]
+    InvalidVoteOutcome,
+    #[msg("No vote found for this voter")]
+    VoteNotFound,
+    #[msg("Vote stake has already been withdrawn")]
+    StakeAlreadyWithdrawn,
+    // Commit-reveal errors
+    #[msg("Reveal period has ended")]
+    RevealPeriodEnded,
+    #[msg("Reveal period has not ended yet")]
+    RevealPeriodNotEnded,
+    #[msg("Vote has already been revealed")]
+    VoteAlreadyRevealed,
+    #[msg("Revealed vote does not match the commitment")]
+    CommitmentMismatch,
+    #[msg("Invalid dispute configuration")]
+    InvalidDisputeConfig,
+    #[msg("Dispute ruling is already final")]
+    DisputeAlreadyFinalized,
+    #[msg("Dispute ruling is not final yet")]
+    DisputeNotFinalized,
+    #[msg("Appeal period has ended")]
+    AppealPeriodEnded,
+    #[msg("Appeal period has not ended yet")]
+    AppealPeriodNotEnded,
+    #[msg("Maximum number of appeals reached")]
+    MaxAppealsReached,
+    #[msg("Claims are paused while the market outcome is disputed")]
+    MarketDisputed,
+    #[msg("Dispute has reached its evidence limit")]
+    EvidenceLimitReached,
+    #[msg("Evidence URI must be non-empty and within the length limit")]
+    InvalidEvidenceUri,
+    #[msg("Fee shares must sum to 10000 basis points")]
+    InvalidFeeSchedule,
+    #[msg("Mint is not this market's token for the outcome")]
+    InvalidOutcomeMint,
+    #[msg("Trading is closed for this market")]
+    TradingClosed,
+    #[msg("Trade price moved beyond the allowed slippage")]
+    SlippageExceeded,
+    #[msg("No fees to claim")]
+    NoFeesToClaim,
+    #[msg("Invalid protocol configuration")]
+    InvalidProtocolConfig,
+    #[msg("This instruction is paused")]
+    InstructionPaused,
+    #[msg("Unknown pause flags")]
+    InvalidPauseFlags,
+    #[msg("Liquidity amount must be greater than zero")]
+    InvalidLiquidityAmount,
+    #[msg("Not enough liquidity in the position or pool")]
+    InsufficientLiquidity,
+    #[msg("Stake amount must be greater than zero")]
+    InvalidStakeAmount,
+    #[msg("Not enough staked creator shares")]
+    InsufficientStake,
+    #[msg("Invalid dynamic fee parameters")]
+    InvalidDynamicFee,
+    #[msg("Creator bond is locked until the lockup after settlement ends")]
+    CreatorBondLocked,
+    #[msg("Market has no creator bond to release")]
+    NoCreatorBond,
+    #[msg("Collateral accounts do not match the market's collateral mint")]
+    InvalidCollateralAccount,
+    #[msg("Metadata URI is too long")]
+    MetadataUriTooLong,
+    #[msg("The dispute window for this market has closed")]
+    DisputeWindowClosed,
+    #[msg("Market settings are locked once trading has started")]
+    MarketAlreadyTraded,
+    #[msg("Liquidity is locked until the deposit lockup ends")]
+    LiquidityLocked,
+    #[msg("Market resolves through optimistic proposals")]
+    OptimisticResolutionEnabled,
 }
\ No newline at end of file
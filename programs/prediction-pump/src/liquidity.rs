@@ -0,0 +1,242 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, mint_to, Burn, Mint, MintTo, Token, TokenAccount};
+
+use crate::{LpPosition, Market, MarketVault, PauseFlag, PredictionPumpError, ProtocolConfig};
+use crate::collateral::{
+    Collateral, CollateralBumps, __client_accounts_collateral, __cpi_client_accounts_collateral,
+};
+
+/// Deposit collateral into a market's liquidity pool in exchange for LP tokens.
+/// The position earns the LP share of trading fees from now on, and is locked
+/// until `LP_LOCKUP` passes or trading closes
+pub(crate) fn add_liquidity(ctx: Context<AddLiquidity>, amount: u64, min_shares: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let position = &mut ctx.accounts.position;
+    let now = Clock::get()?.unix_timestamp;
+    market.require_trading_open(now)?;
+    require!(amount > 0, PredictionPumpError::InvalidLiquidityAmount);
+
+    let shares = market.liquidity.shares_for_deposit(amount)?;
+    require!(shares > 0 && shares >= min_shares, PredictionPumpError::SlippageExceeded);
+
+    ctx.accounts.collateral.deposit(
+        market,
+        &ctx.accounts.market_vault.to_account_info(),
+        &ctx.accounts.provider.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        amount,
+    )?;
+
+    let market_key = market.key();
+    let vault_seeds: &[&[u8]] = &[
+        b"market_vault",
+        market_key.as_ref(),
+        &[ctx.accounts.market_vault.bump],
+    ];
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.provider_lp_account.to_account_info(),
+                authority: ctx.accounts.market_vault.to_account_info(),
+            },
+            &[vault_seeds],
+        ),
+        shares,
+    )?;
+
+    if position.owner == Pubkey::default() {
+        position.market = market_key;
+        position.owner = ctx.accounts.provider.key();
+        position.bump = ctx.bumps.position;
+    }
+    position.deposit(shares, market.liquidity.fee_per_share)?;
+    position.lock_at(now, market.resolution_date);
+    market.liquidity.deposit(amount, shares)?;
+
+    emit!(LiquidityAddedEvent {
+        market: market_key,
+        provider: position.owner,
+        amount,
+        shares,
+    });
+
+    Ok(())
+}
+
+/// Burn LP tokens for their share of pool collateral, plus every fee the
+/// position has accrued
+pub(crate) fn remove_liquidity(ctx: Context<RemoveLiquidity>, shares: u64, min_amount: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let position = &mut ctx.accounts.position;
+    require!(shares > 0, PredictionPumpError::InvalidLiquidityAmount);
+    position.require_unlocked_at(Clock::get()?.unix_timestamp)?;
+
+    let collateral = market.liquidity.collateral_for_shares(shares)?;
+    let fees = position.withdraw(shares, market.liquidity.fee_per_share)?;
+    let amount = collateral.checked_add(fees).ok_or(PredictionPumpError::MathOverflow)?;
+    require!(amount >= min_amount, PredictionPumpError::SlippageExceeded);
+
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.provider_lp_account.to_account_info(),
+                authority: ctx.accounts.provider.to_account_info(),
+            },
+        ),
+        shares,
+    )?;
+
+    market.liquidity.withdraw(collateral, shares)?;
+    market.fees.lp_claimed = market
+        .fees
+        .lp_claimed
+        .checked_add(fees)
+        .ok_or(PredictionPumpError::MathOverflow)?;
+
+    ctx.accounts.collateral.withdraw(
+        market,
+        &ctx.accounts.market_vault.to_account_info(),
+        ctx.accounts.market_vault.bump,
+        &ctx.accounts.provider.to_account_info(),
+        amount,
+    )?;
+
+    emit!(LiquidityRemovedEvent {
+        market: market.key(),
+        provider: position.owner,
+        shares,
+        collateral,
+        fees,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddLiquidity<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market_vault.bump
+    )]
+    pub market_vault: Account<'info, MarketVault>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_mint", market.key().as_ref()],
+        bump
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    /// Fee accounting for this provider's deposits
+    #[account(
+        init_if_needed,
+        payer = provider,
+        space = LpPosition::LEN,
+        seeds = [b"lp_position", market.key().as_ref(), provider.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, LpPosition>,
+
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = provider
+    )]
+    pub provider_lp_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub provider: Signer<'info>,
+
+    /// Collateral mint and token accounts; omitted for SOL markets
+    pub collateral: Collateral<'info>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(AddLiquidity::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveLiquidity<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market_vault.bump
+    )]
+    pub market_vault: Account<'info, MarketVault>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_mint", market.key().as_ref()],
+        bump
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_position", market.key().as_ref(), provider.key().as_ref()],
+        bump = position.bump,
+        has_one = market,
+        constraint = position.owner == provider.key() @ PredictionPumpError::Unauthorized
+    )]
+    pub position: Account<'info, LpPosition>,
+
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = provider
+    )]
+    pub provider_lp_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub provider: Signer<'info>,
+
+    /// Collateral mint and token accounts; omitted for SOL markets
+    pub collateral: Collateral<'info>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(RemoveLiquidity::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+// Events for off-chain indexing and real-time updates
+
+#[event]
+pub struct LiquidityAddedEvent {
+    pub market: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+}
+
+#[event]
+pub struct LiquidityRemovedEvent {
+    pub market: Pubkey,
+    pub provider: Pubkey,
+    pub shares: u64,
+    pub collateral: u64,
+    pub fees: u64,
+}
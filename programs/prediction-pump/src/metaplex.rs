@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::Mint;
+
+use crate::{outcome_mint_name, Market, MarketVault, PauseFlag, PredictionPumpError, ProtocolConfig, OUTCOME_SYMBOLS};
+
+/// Metaplex Token Metadata program
+/// (`metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s`)
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+    11, 112, 101, 177, 227, 209, 124, 69, 56, 157, 82, 127, 107, 4, 195, 205,
+    88, 184, 108, 115, 26, 160, 253, 181, 73, 182, 209, 188, 3, 248, 41, 70,
+]);
+
+/// Instruction index of `CreateMetadataAccountV3`
+const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+
+// Field limits enforced by Token Metadata
+pub const MAX_METADATA_NAME_LEN: usize = 32;
+pub const MAX_METADATA_URI_LEN: usize = 200;
+
+/// Metadata name of an outcome mint: the full mint name, with the market
+/// description cut short when it would not fit Token Metadata's name limit
+pub fn metadata_outcome_name(description: &str, outcome: u8) -> String {
+    let name = outcome_mint_name(description, outcome);
+    if name.len() <= MAX_METADATA_NAME_LEN {
+        return name;
+    }
+
+    let suffix_len = name.len() - description.len();
+    let mut end = MAX_METADATA_NAME_LEN - suffix_len;
+    while !description.is_char_boundary(end) {
+        end -= 1;
+    }
+    outcome_mint_name(description[..end].trim_end(), outcome)
+}
+
+/// Borsh-encoded `CreateMetadataAccountV3` data for a mutable fungible token
+/// with no creators, collection, uses or collection details
+pub fn create_metadata_account_v3_data(name: String, symbol: String, uri: String) -> Result<Vec<u8>> {
+    let mut data = vec![CREATE_METADATA_ACCOUNT_V3];
+    // name, symbol, uri, seller_fee_basis_points
+    (name, symbol, uri, 0u16).serialize(&mut data)?;
+    // creators, collection, uses: None; is_mutable: true; collection_details: None
+    data.extend_from_slice(&[0, 0, 0, 1, 0]);
+    Ok(data)
+}
+
+/// Create the Token Metadata account of an outcome mint so wallets show its
+/// name and symbol. The market vault signs as mint and update authority
+pub(crate) fn create_outcome_metadata(ctx: Context<CreateOutcomeMetadata>, outcome: u8, uri: String) -> Result<()> {
+    let market = &ctx.accounts.market;
+
+    require!(
+        market.outcome_mint(outcome)? == ctx.accounts.outcome_mint.key(),
+        PredictionPumpError::InvalidOutcomeMint
+    );
+    require!(uri.len() <= MAX_METADATA_URI_LEN, PredictionPumpError::MetadataUriTooLong);
+
+    let metadata = &ctx.accounts.metadata;
+    let outcome_mint = &ctx.accounts.outcome_mint;
+    let market_vault = &ctx.accounts.market_vault;
+    let creator = &ctx.accounts.creator;
+
+    let instruction = Instruction {
+        program_id: TOKEN_METADATA_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(metadata.key(), false),
+            AccountMeta::new_readonly(outcome_mint.key(), false),
+            AccountMeta::new_readonly(market_vault.key(), true), // mint authority
+            AccountMeta::new(creator.key(), true),               // payer
+            AccountMeta::new_readonly(market_vault.key(), true), // update authority
+            AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.rent.key(), false),
+        ],
+        data: create_metadata_account_v3_data(
+            metadata_outcome_name(&market.description, outcome),
+            OUTCOME_SYMBOLS[outcome as usize].to_string(),
+            uri,
+        )?,
+    };
+
+    let market_key = market.key();
+    let vault_seeds: &[&[u8]] = &[b"market_vault", market_key.as_ref(), &[market_vault.bump]];
+    invoke_signed(
+        &instruction,
+        &[
+            metadata.to_account_info(),
+            outcome_mint.to_account_info(),
+            market_vault.to_account_info(),
+            creator.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+        ],
+        &[vault_seeds],
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateOutcomeMetadata<'info> {
+    #[account(has_one = creator @ PredictionPumpError::Unauthorized)]
+    pub market: Account<'info, Market>,
+
+    /// Mint and update authority of the outcome mints
+    #[account(
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market_vault.bump
+    )]
+    pub market_vault: Account<'info, MarketVault>,
+
+    /// Outcome mint being described, validated against the market
+    pub outcome_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), outcome_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    /// CHECK: Metadata PDA created by the Token Metadata program
+    pub metadata: UncheckedAccount<'info>,
+
+    /// Pays for the metadata account
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(address = TOKEN_METADATA_PROGRAM_ID)]
+    /// CHECK: Token Metadata program, checked by address
+    pub token_metadata_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(CreateOutcomeMetadata::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_metadata_program_id() {
+        assert_eq!(
+            TOKEN_METADATA_PROGRAM_ID.to_string(),
+            "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+        );
+    }
+
+    #[test]
+    fn test_metadata_name_keeps_outcome_suffix() {
+        assert_eq!(metadata_outcome_name("Rain in NYC?", 0), "Rain in NYC? – YES");
+
+        let name = metadata_outcome_name("Will BTC reach $100k by end of 2024?", 1);
+        assert_eq!(name, "Will BTC reach $100k by e – NO");
+        assert!(name.len() <= MAX_METADATA_NAME_LEN);
+    }
+
+    #[test]
+    fn test_metadata_name_cuts_on_char_boundary() {
+        let name = metadata_outcome_name(&"é".repeat(30), 0);
+
+        assert!(name.len() <= MAX_METADATA_NAME_LEN);
+        assert!(name.ends_with(" – YES"));
+    }
+
+    #[test]
+    fn test_create_metadata_account_v3_data_layout() {
+        let data = create_metadata_account_v3_data("Rain – YES".into(), "YES".into(), "".into()).unwrap();
+
+        assert_eq!(data[0], CREATE_METADATA_ACCOUNT_V3);
+        assert_eq!(&data[1..5], &("Rain – YES".len() as u32).to_le_bytes());
+        assert_eq!(&data[5..5 + "Rain – YES".len()], "Rain – YES".as_bytes());
+        // symbol, empty uri, zero seller fee, then the option and flag bytes
+        assert_eq!(data.len(), 1 + 4 + "Rain – YES".len() + 4 + 3 + 4 + 2 + 5);
+        assert_eq!(&data[data.len() - 5..], &[0, 0, 0, 1, 0]);
+    }
+}
@@ -0,0 +1,531 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::{
+    settlement, Dispute, DisputeConfig, DisputeOutcome, Market, OracleData, PauseFlag,
+    PredictionPumpError, ProtocolConfig, VoteEscrow,
+    MAX_DISPUTE_REASON_LEN, VOID_MARKET_VOTE,
+};
+use crate::collateral::{
+    Collateral, CollateralBumps, __client_accounts_collateral, __cpi_client_accounts_collateral,
+};
+
+/// Confidence recorded for bonded proposals; the bond stands in for oracle confidence
+pub const OPTIMISTIC_CONFIDENCE_SCORE: u16 = 10000;
+
+/// Propose a market outcome by posting the market's bond into the proposal escrow
+pub(crate) fn propose_outcome(ctx: Context<ProposeOutcome>, proposed_outcome: u8) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let proposal = &mut ctx.accounts.proposal;
+    let oracle_data = &mut ctx.accounts.oracle_data;
+    let proposer = ctx.accounts.proposer.key();
+    let clock = Clock::get()?;
+
+    let config = market
+        .optimistic_config
+        .clone()
+        .ok_or(PredictionPumpError::OptimisticResolutionDisabled)?;
+
+    // Validate proposal
+    require!(!market.status.is_settled, PredictionPumpError::MarketAlreadySettled);
+    require!(
+        clock.unix_timestamp >= market.resolution_date,
+        PredictionPumpError::MarketNotYetResolved
+    );
+    require!(
+        (proposed_outcome as usize) < market.outcome_tokens.len(),
+        PredictionPumpError::InvalidWinningOutcome
+    );
+
+    // Post the bond into the proposal escrow
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.proposer.to_account_info(),
+                to: proposal.to_account_info(),
+            },
+        ),
+        config.bond_amount,
+    )?;
+
+    // The proposal is recorded as oracle data so disputes can use the existing flow
+    **oracle_data = OracleData::new(
+        market.key(),
+        proposer,
+        proposed_outcome,
+        OPTIMISTIC_CONFIDENCE_SCORE,
+        Vec::new(),
+    )?;
+
+    **proposal = Proposal::new(
+        market.key(),
+        oracle_data.key(),
+        proposer,
+        proposed_outcome,
+        config.bond_amount,
+        clock.unix_timestamp,
+        config.liveness,
+        ctx.bumps.proposal,
+    )?;
+
+    emit!(OutcomeProposedEvent {
+        market: market.key(),
+        proposer,
+        proposed_outcome,
+        bond: config.bond_amount,
+        liveness_end: proposal.liveness_end,
+    });
+
+    Ok(())
+}
+
+/// Dispute a pending proposal by matching its bond; the dispute is decided by voting
+pub(crate) fn dispute_proposal(ctx: Context<DisputeProposal>, reason: String) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let oracle_data = &mut ctx.accounts.oracle_data;
+    let dispute = &mut ctx.accounts.dispute;
+    let disputer = ctx.accounts.disputer.key();
+
+    // Validate dispute submission
+    require!(
+        proposal.is_open(Clock::get()?.unix_timestamp),
+        PredictionPumpError::LivenessPeriodEnded
+    );
+    require!(reason.len() <= MAX_DISPUTE_REASON_LEN, PredictionPumpError::DisputeReasonTooLong);
+
+    // Post the counter-bond into the same escrow
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.disputer.to_account_info(),
+                to: proposal.to_account_info(),
+            },
+        ),
+        proposal.bond,
+    )?;
+
+    // Pause claims until the ruling is final, as for any other dispute
+    oracle_data.dispute()?;
+    ctx.accounts.market.status.is_disputed = true;
+    proposal.record_dispute(disputer, dispute.key())?;
+
+    **dispute = Dispute::new(
+        proposal.market,
+        oracle_data.key(),
+        disputer,
+        reason,
+        proposal.bond,
+        &ctx.accounts.dispute_config,
+    )?;
+    // The proposer's bond joins the voters' reward pool if the proposal is overturned
+    dispute.bond_pending = true;
+
+    emit!(ProposalDisputedEvent {
+        market: proposal.market,
+        disputer,
+        dispute: dispute.key(),
+    });
+
+    Ok(())
+}
+
+/// Finalize a proposal: settle the market and release the escrowed bonds. A proposer
+/// who was right takes both bonds; an overturned proposer's bond goes to the dispute's
+/// reward pool and the disputer gets their own bond back
+pub(crate) fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let proposal = &mut ctx.accounts.proposal;
+    let oracle_data = &ctx.accounts.oracle_data;
+    let clock = Clock::get()?;
+
+    require!(!proposal.is_finalized, PredictionPumpError::ProposalAlreadyFinalized);
+    // A market voided by dispute only needs its bonds released
+    require!(
+        !market.status.is_settled || market.status.is_voided,
+        PredictionPumpError::MarketAlreadySettled
+    );
+
+    // Undisputed proposals finalize after liveness; disputed ones once voting resolved
+    let winning_outcome = match proposal.dispute {
+        None => {
+            require!(
+                !proposal.is_open(clock.unix_timestamp),
+                PredictionPumpError::LivenessPeriodNotEnded
+            );
+            proposal.proposed_outcome
+        }
+        Some(dispute_key) => {
+            let dispute = ctx
+                .accounts
+                .dispute
+                .as_mut()
+                .ok_or(PredictionPumpError::DisputeNotResolved)?;
+            require!(dispute.key() == dispute_key, PredictionPumpError::DisputeNotResolved);
+            require!(dispute.is_finalized, PredictionPumpError::DisputeNotFinalized);
+            require!(!oracle_data.is_disputed, PredictionPumpError::DisputedOracleData);
+            dispute.bond_pending = false;
+            match dispute.resolution.as_ref().map(|resolution| &resolution.outcome) {
+                Some(DisputeOutcome::VoidMarket) => VOID_MARKET_VOTE,
+                _ => oracle_data.winning_outcome,
+            }
+        }
+    };
+
+    // Release the bonds to whoever was right
+    let bond_winner = proposal.bond_winner(winning_outcome);
+    require!(
+        ctx.accounts.bond_recipient.key() == bond_winner,
+        PredictionPumpError::InvalidBondRecipient
+    );
+
+    let mut escrowed = proposal.escrowed_bond()?;
+    let proposal_info = proposal.to_account_info();
+
+    if proposal.disputer.is_some() && winning_outcome != proposal.proposed_outcome {
+        let dispute = ctx
+            .accounts
+            .dispute
+            .as_mut()
+            .ok_or(PredictionPumpError::DisputeNotResolved)?;
+        let vote_escrow = ctx
+            .accounts
+            .vote_escrow
+            .as_mut()
+            .ok_or(PredictionPumpError::InvalidBondRecipient)?;
+        require!(
+            vote_escrow.dispute == dispute.key(),
+            PredictionPumpError::InvalidBondRecipient
+        );
+
+        let vote_escrow_info = vote_escrow.to_account_info();
+        **proposal_info.try_borrow_mut_lamports()? = proposal_info
+            .lamports()
+            .checked_sub(proposal.bond)
+            .ok_or(PredictionPumpError::InsufficientVaultFunds)?;
+        **vote_escrow_info.try_borrow_mut_lamports()? = vote_escrow_info
+            .lamports()
+            .checked_add(proposal.bond)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+
+        vote_escrow.total_locked = vote_escrow
+            .total_locked
+            .checked_add(proposal.bond)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        dispute.oracle_bond = proposal.bond;
+        escrowed = escrowed
+            .checked_sub(proposal.bond)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+    }
+
+    let recipient_info = ctx.accounts.bond_recipient.to_account_info();
+
+    **proposal_info.try_borrow_mut_lamports()? = proposal_info
+        .lamports()
+        .checked_sub(escrowed)
+        .ok_or(PredictionPumpError::InsufficientVaultFunds)?;
+
+    **recipient_info.try_borrow_mut_lamports()? = recipient_info
+        .lamports()
+        .checked_add(escrowed)
+        .ok_or(PredictionPumpError::MathOverflow)?;
+
+    proposal.is_finalized = true;
+
+    emit!(ProposalFinalizedEvent {
+        market: market.key(),
+        winning_outcome,
+        bond_recipient: bond_winner,
+        bond_amount: escrowed,
+    });
+
+    if market.status.is_voided {
+        return Ok(());
+    }
+
+    let total_payout = ctx.accounts.collateral.balance(market, &ctx.accounts.market_vault.to_account_info())?;
+
+    settlement::record_settlement(
+        market,
+        winning_outcome,
+        oracle_data.data_hash,
+        total_payout,
+        clock.unix_timestamp,
+    )
+}
+
+/// Optimistic resolution settings chosen by the market creator
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct OptimisticConfig {
+    pub bond_amount: u64, // lamports posted by proposer and by disputer
+    pub liveness: i64,    // seconds a proposal stays open to disputes
+}
+
+impl OptimisticConfig {
+    pub const LEN: usize = 8 + // bond_amount
+        8; // liveness
+
+    pub fn new(bond_amount: u64, liveness: i64) -> Result<Self> {
+        require!(bond_amount > 0, PredictionPumpError::InvalidOptimisticConfig);
+        require!(liveness > 0, PredictionPumpError::InvalidOptimisticConfig);
+
+        Ok(OptimisticConfig {
+            bond_amount,
+            liveness,
+        })
+    }
+}
+
+/// Bonded outcome proposal; the account itself escrows the bonds
+#[account]
+pub struct Proposal {
+    pub market: Pubkey,
+    pub oracle_data: Pubkey,
+    pub proposer: Pubkey,
+    pub proposed_outcome: u8,
+    pub bond: u64,
+    pub proposal_time: i64,
+    pub liveness_end: i64,
+    pub disputer: Option<Pubkey>,
+    pub dispute: Option<Pubkey>,
+    pub is_finalized: bool,
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // market
+        32 + // oracle_data
+        32 + // proposer
+        1 + // proposed_outcome
+        8 + // bond
+        8 + // proposal_time
+        8 + // liveness_end
+        1 + 32 + // disputer (Option)
+        1 + 32 + // dispute (Option)
+        1 + // is_finalized
+        1; // bump
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        market: Pubkey,
+        oracle_data: Pubkey,
+        proposer: Pubkey,
+        proposed_outcome: u8,
+        bond: u64,
+        proposal_time: i64,
+        liveness: i64,
+        bump: u8,
+    ) -> Result<Self> {
+        let liveness_end = proposal_time
+            .checked_add(liveness)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+
+        Ok(Proposal {
+            market,
+            oracle_data,
+            proposer,
+            proposed_outcome,
+            bond,
+            proposal_time,
+            liveness_end,
+            disputer: None,
+            dispute: None,
+            is_finalized: false,
+            bump,
+        })
+    }
+
+    /// Whether the proposal can still be disputed
+    pub fn is_open(&self, current_time: i64) -> bool {
+        self.dispute.is_none() && current_time < self.liveness_end
+    }
+
+    pub fn record_dispute(&mut self, disputer: Pubkey, dispute: Pubkey) -> Result<()> {
+        require!(self.dispute.is_none(), PredictionPumpError::AlreadyDisputed);
+        require!(!self.is_finalized, PredictionPumpError::ProposalAlreadyFinalized);
+
+        self.disputer = Some(disputer);
+        self.dispute = Some(dispute);
+        Ok(())
+    }
+
+    /// Lamports held in escrow: the proposer's bond plus any counter-bond
+    pub fn escrowed_bond(&self) -> Result<u64> {
+        if self.disputer.is_some() {
+            self.bond
+                .checked_mul(2)
+                .ok_or(PredictionPumpError::MathOverflow.into())
+        } else {
+            Ok(self.bond)
+        }
+    }
+
+    /// Account entitled to the escrowed bonds once the final outcome is known
+    pub fn bond_winner(&self, final_outcome: u8) -> Pubkey {
+        match self.disputer {
+            Some(disputer) if final_outcome != self.proposed_outcome => disputer,
+            _ => self.proposer,
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct ProposeOutcome<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::LEN,
+        seeds = [b"proposal", market.key().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = OracleData::LEN,
+        seeds = [b"oracle_data", market.key().as_ref()],
+        bump
+    )]
+    pub oracle_data: Account<'info, OracleData>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(ProposeOutcome::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.market.as_ref()],
+        bump = proposal.bump,
+        has_one = market,
+        has_one = oracle_data
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Market the proposal resolves, whose pause bits apply; claims pause while disputed
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(mut)]
+    pub oracle_data: Account<'info, OracleData>,
+
+    #[account(
+        seeds = [b"dispute_config"],
+        bump = dispute_config.bump
+    )]
+    pub dispute_config: Account<'info, DisputeConfig>,
+
+    #[account(
+        init,
+        payer = disputer,
+        space = Dispute::LEN
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(DisputeProposal::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", market.key().as_ref()],
+        bump = proposal.bump,
+        has_one = market,
+        has_one = oracle_data
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub oracle_data: Account<'info, OracleData>,
+
+    /// Resolved dispute, required only when the proposal was disputed
+    #[account(mut)]
+    pub dispute: Option<Account<'info, Dispute>>,
+
+    /// The dispute's vote escrow, required only when voting overturned the proposal
+    #[account(mut)]
+    pub vote_escrow: Option<Account<'info, VoteEscrow>>,
+
+    /// Proposer, or the disputer if voting overturned the proposal
+    #[account(mut)]
+    /// CHECK: Validated against the proposal's bond winner in the instruction
+    pub bond_recipient: UncheckedAccount<'info>,
+
+    /// Market vault holding SOL for payouts
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Market vault is validated by seeds
+    pub market_vault: UncheckedAccount<'info>,
+
+    /// Anyone can finalize once liveness ends or the dispute is resolved
+    pub settler: Signer<'info>,
+
+    /// Collateral mint and vault token account; omitted for SOL markets
+    pub collateral: Collateral<'info>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(FinalizeProposal::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+// Events for off-chain indexing and real-time updates
+
+#[event]
+pub struct OutcomeProposedEvent {
+    pub market: Pubkey,
+    pub proposer: Pubkey,
+    pub proposed_outcome: u8,
+    pub bond: u64,
+    pub liveness_end: i64,
+}
+
+#[event]
+pub struct ProposalDisputedEvent {
+    pub market: Pubkey,
+    pub disputer: Pubkey,
+    pub dispute: Pubkey,
+}
+
+#[event]
+pub struct ProposalFinalizedEvent {
+    pub market: Pubkey,
+    pub winning_outcome: u8,
+    pub bond_recipient: Pubkey,
+    pub bond_amount: u64,
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::system_program::{create_account, CreateAccount};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{metadata_pointer, ExtensionType},
+    state::Mint as MintState,
+};
+use anchor_spl::token_interface::{initialize_mint2, InitializeMint2};
+use spl_token_metadata_interface::{instruction as token_metadata, state::TokenMetadata};
+
+use crate::CreateMarket;
+
+/// Decimals of every outcome mint
+pub const OUTCOME_DECIMALS: u8 = 6;
+
+/// Token symbol of each outcome of a binary market
+pub const OUTCOME_SYMBOLS: [&str; 2] = ["YES", "NO"];
+
+/// Display name of an outcome mint, e.g. "Will it rain? – YES"
+pub fn outcome_mint_name(description: &str, outcome: u8) -> String {
+    format!("{} – {}", description, OUTCOME_SYMBOLS[outcome as usize])
+}
+
+impl<'info> CreateMarket<'info> {
+    /// Create outcome mint `outcome` under the outcome token program with the
+    /// market vault as mint authority. Token-2022 mints also carry their name
+    /// and symbol in a metadata extension the metadata pointer points back at
+    pub fn create_outcome_mint(&self, outcome: u8, description: &str, vault_bump: u8) -> Result<()> {
+        let mint = match outcome {
+            0 => self.outcome_mint_0.to_account_info(),
+            _ => self.outcome_mint_1.to_account_info(),
+        };
+        let market_vault = self.market_vault.to_account_info();
+        let token_program = self.outcome_token_program.to_account_info();
+
+        let with_metadata = token_program.key() == spl_token_2022::ID;
+        let metadata = TokenMetadata {
+            name: outcome_mint_name(description, outcome),
+            symbol: OUTCOME_SYMBOLS[outcome as usize].to_string(),
+            ..Default::default()
+        };
+
+        let (extensions, metadata_space) = if with_metadata {
+            (vec![ExtensionType::MetadataPointer], metadata.tlv_size_of()?)
+        } else {
+            (vec![], 0)
+        };
+        let space = ExtensionType::try_calculate_account_len::<MintState>(&extensions)?;
+
+        // Fund the metadata up front; the token program reallocates the mint into it
+        let lamports = Rent::get()?.minimum_balance(space + metadata_space);
+        create_account(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                CreateAccount {
+                    from: self.creator.to_account_info(),
+                    to: mint.clone(),
+                },
+            ),
+            lamports,
+            space as u64,
+            token_program.key,
+        )?;
+
+        if with_metadata {
+            invoke(
+                &metadata_pointer::instruction::initialize(
+                    token_program.key,
+                    mint.key,
+                    Some(market_vault.key()),
+                    Some(mint.key()),
+                )?,
+                std::slice::from_ref(&mint),
+            )?;
+        }
+
+        initialize_mint2(
+            CpiContext::new(token_program.clone(), InitializeMint2 { mint: mint.clone() }),
+            OUTCOME_DECIMALS,
+            market_vault.key,
+            None,
+        )?;
+
+        if with_metadata {
+            let market_key = self.market.key();
+            let vault_seeds: &[&[u8]] = &[b"market_vault", market_key.as_ref(), &[vault_bump]];
+            invoke_signed(
+                &token_metadata::initialize(
+                    token_program.key,
+                    mint.key,
+                    market_vault.key,
+                    mint.key,
+                    market_vault.key,
+                    metadata.name,
+                    metadata.symbol,
+                    metadata.uri,
+                ),
+                &[mint, market_vault],
+                &[vault_seeds],
+            )?;
+        }
+
+        Ok(())
+    }
+}
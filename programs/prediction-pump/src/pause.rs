@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+
+use crate::*;
+
+// Pause bits, set globally on ProtocolConfig or per market on Market
+pub const PAUSE_TRADING: u8 = 1 << 0; // buying, selling, deposits and market creation
+pub const PAUSE_SETTLEMENT: u8 = 1 << 1; // oracle reports, proposals and settlement
+pub const PAUSE_CLAIMS: u8 = 1 << 2; // payouts, fee claims, withdrawals, stake refunds and rewards
+pub const PAUSE_DISPUTES: u8 = 1 << 3; // disputes, evidence, votes and appeals
+pub const PAUSE_ALL: u8 = PAUSE_TRADING | PAUSE_SETTLEMENT | PAUSE_CLAIMS | PAUSE_DISPUTES;
+
+/// Pause bit an instruction's accounts check before it runs
+pub trait PauseFlag {
+    const PAUSE_FLAG: u8;
+}
+
+macro_rules! pause_flags {
+    ($($accounts:ident => $flag:expr,)*) => {
+        $(impl PauseFlag for $accounts<'_> {
+            const PAUSE_FLAG: u8 = $flag;
+        })*
+    };
+}
+
+pause_flags! {
+    CreateMarket => PAUSE_TRADING,
+    BuyTokens => PAUSE_TRADING,
+    SellTokens => PAUSE_TRADING,
+    AddLiquidity => PAUSE_TRADING,
+    InitializeCreatorShares => PAUSE_TRADING,
+    StakeCreatorShares => PAUSE_TRADING,
+    SetDynamicFee => PAUSE_TRADING,
+    CreateOutcomeMetadata => PAUSE_TRADING,
+    SetResolutionCondition => PAUSE_SETTLEMENT,
+    SetOptimisticConfig => PAUSE_SETTLEMENT,
+    SettleMarket => PAUSE_SETTLEMENT,
+    SettleMarketSwitchboard => PAUSE_SETTLEMENT,
+    SubmitSignedReport => PAUSE_SETTLEMENT,
+    ProposeOutcome => PAUSE_SETTLEMENT,
+    FinalizeProposal => PAUSE_SETTLEMENT,
+    ClaimPayout => PAUSE_CLAIMS,
+    ReleaseCreatorBond => PAUSE_CLAIMS,
+    ClaimCreatorFees => PAUSE_CLAIMS,
+    ClaimUnsharedCreatorFees => PAUSE_CLAIMS,
+    ClaimProtocolFees => PAUSE_CLAIMS,
+    RemoveLiquidity => PAUSE_CLAIMS,
+    UnstakeCreatorShares => PAUSE_CLAIMS,
+    WithdrawVoteStake => PAUSE_CLAIMS,
+    ClaimDisputeReward => PAUSE_CLAIMS,
+    SettleDisputeStake => PAUSE_CLAIMS,
+    SettleAppealStake => PAUSE_CLAIMS,
+    SubmitDispute => PAUSE_DISPUTES,
+    DisputeProposal => PAUSE_DISPUTES,
+    AddEvidence => PAUSE_DISPUTES,
+    CommitVote => PAUSE_DISPUTES,
+    RevealVote => PAUSE_DISPUTES,
+    ResolveDispute => PAUSE_DISPUTES,
+    AppealDispute => PAUSE_DISPUTES,
+    FinalizeDispute => PAUSE_DISPUTES,
+}
+
+/// Set the protocol-wide pause bits
+pub(crate) fn set_protocol_pause(ctx: Context<SetProtocolPause>, paused: u8) -> Result<()> {
+    require!(paused & !PAUSE_ALL == 0, PredictionPumpError::InvalidPauseFlags);
+    ctx.accounts.protocol_config.paused = paused;
+
+    emit!(PauseUpdatedEvent {
+        market: None,
+        paused,
+        updated_by: ctx.accounts.guardian.key(),
+    });
+
+    Ok(())
+}
+
+/// Set one market's pause bits; they apply on top of the protocol-wide bits to
+/// every instruction that takes the market account
+pub(crate) fn set_market_pause(ctx: Context<SetMarketPause>, paused: u8) -> Result<()> {
+    require!(paused & !PAUSE_ALL == 0, PredictionPumpError::InvalidPauseFlags);
+    ctx.accounts.market.paused = paused;
+
+    emit!(PauseUpdatedEvent {
+        market: Some(ctx.accounts.market.key()),
+        paused,
+        updated_by: ctx.accounts.guardian.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetProtocolPause<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = protocol_config.can_pause(&guardian.key()) @ PredictionPumpError::Unauthorized
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// The guardian, or the admin
+    pub guardian: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMarketPause<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = protocol_config.can_pause(&guardian.key()) @ PredictionPumpError::Unauthorized
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// The guardian, or the admin
+    pub guardian: Signer<'info>,
+}
+
+#[event]
+pub struct PauseUpdatedEvent {
+    pub market: Option<Pubkey>, // None for the protocol-wide bits
+    pub paused: u8,
+    pub updated_by: Pubkey,
+}
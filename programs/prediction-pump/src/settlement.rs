@@ -1,10 +1,13 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount, burn, Burn};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, burn, Burn};
 
-use crate::{Market, MarketStatus, SettlementData, OracleData, PredictionPumpError};
+use crate::{Market, MarketVault, PauseFlag, ProtocolConfig, MarketStatus, SettlementData, OracleData, PredictionPumpError, VOID_MARKET_VOTE};
+use crate::collateral::{
+    Collateral, CollateralBumps, __client_accounts_collateral, __cpi_client_accounts_collateral,
+};
 
 /// Settle a market using oracle data and distribute payouts
-pub fn settle_market(ctx: Context<SettleMarket>) -> Result<()> {
+pub(crate) fn settle_market(ctx: Context<SettleMarket>) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let oracle_data = &ctx.accounts.oracle_data;
     let clock = Clock::get()?;
@@ -15,6 +18,11 @@ pub fn settle_market(ctx: Context<SettleMarket>) -> Result<()> {
         clock.unix_timestamp >= market.resolution_date,
         PredictionPumpError::MarketNotYetResolved
     );
+    // Optimistic markets settle only through finalize_proposal, which releases the bonds
+    require!(
+        market.optimistic_config.is_none(),
+        PredictionPumpError::OptimisticResolutionEnabled
+    );
 
     // Validate oracle data
     require!(
@@ -36,94 +44,189 @@ pub fn settle_market(ctx: Context<SettleMarket>) -> Result<()> {
         PredictionPumpError::CorruptedOracleData
     );
 
+    // Calculate total payout from market vault
+    let total_payout = ctx.accounts.collateral.balance(market, &ctx.accounts.market_vault.to_account_info())?;
+
+    record_settlement(
+        market,
+        oracle_data.winning_outcome,
+        oracle_data.data_hash,
+        total_payout,
+        clock.unix_timestamp,
+    )
+}
+
+/// Mark a market as settled on the given outcome and emit the settlement event
+pub(crate) fn record_settlement(
+    market: &mut Account<Market>,
+    winning_outcome: u8,
+    oracle_data_hash: [u8; 32],
+    total_payout: u64,
+    settlement_timestamp: i64,
+) -> Result<()> {
     // Validate winning outcome is valid for this market
     require!(
-        (oracle_data.winning_outcome as usize) < market.outcome_tokens.len(),
+        (winning_outcome as usize) < market.outcome_tokens.len(),
         PredictionPumpError::InvalidWinningOutcome
     );
-
-    // Calculate total payout from market vault
-    let total_payout = **ctx.accounts.market_vault.to_account_info().lamports.borrow();
+    require!(!market.status.is_disputed, PredictionPumpError::MarketDisputed);
 
     // Update market status
     market.status.is_settled = true;
-    market.status.winning_outcome = Some(oracle_data.winning_outcome);
-    market.status.settlement_timestamp = Some(clock.unix_timestamp);
+    market.status.winning_outcome = Some(winning_outcome);
+    market.status.settlement_timestamp = Some(settlement_timestamp);
 
     // Create settlement data
     market.settlement_data = Some(SettlementData {
-        winning_outcome: oracle_data.winning_outcome,
-        settlement_timestamp: clock.unix_timestamp,
-        oracle_data_hash: oracle_data.data_hash,
+        winning_outcome,
+        settlement_timestamp,
+        oracle_data_hash,
         total_payout,
+        winning_supply: 0,
+        total_claimed: 0,
     });
 
     // Emit settlement event
     emit!(MarketSettledEvent {
         market: market.key(),
-        winning_outcome: oracle_data.winning_outcome,
+        winning_outcome,
+        total_payout,
+        settlement_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Move a settled market to the outcome chosen by a dispute ruling
+pub(crate) fn override_settlement(
+    market: &mut Account<Market>,
+    winning_outcome: u8,
+    oracle_data_hash: [u8; 32],
+) -> Result<()> {
+    require!(
+        (winning_outcome as usize) < market.outcome_tokens.len(),
+        PredictionPumpError::InvalidWinningOutcome
+    );
+
+    let settlement_data = market
+        .settlement_data
+        .as_mut()
+        .ok_or(PredictionPumpError::NoSettlementData)?;
+    settlement_data.override_outcome(winning_outcome, oracle_data_hash)?;
+    let total_payout = settlement_data.total_payout;
+    let settlement_timestamp = settlement_data.settlement_timestamp;
+
+    market.status.winning_outcome = Some(winning_outcome);
+
+    emit!(MarketSettledEvent {
+        market: market.key(),
+        winning_outcome,
+        total_payout,
+        settlement_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Void a market ruled malformed by dispute. It settles with no winner, and the
+/// remaining payout pool is shared by every outcome token still outstanding
+pub(crate) fn void_market(
+    market: &mut Account<Market>,
+    outcome_supply: u64,
+    collateral: u64,
+    settlement_timestamp: i64,
+) -> Result<()> {
+    // Claims already paid on a settled market are final
+    let (total_payout, oracle_data_hash) = match &market.settlement_data {
+        Some(settlement_data) => (settlement_data.unclaimed_payout()?, settlement_data.oracle_data_hash),
+        None => (collateral, [0u8; 32]),
+    };
+
+    market.status.is_settled = true;
+    market.status.is_voided = true;
+    market.status.winning_outcome = None;
+    market.status.settlement_timestamp = Some(settlement_timestamp);
+
+    market.settlement_data = Some(SettlementData {
+        winning_outcome: VOID_MARKET_VOTE,
+        settlement_timestamp,
+        oracle_data_hash,
+        total_payout,
+        winning_supply: outcome_supply,
+        total_claimed: 0,
+    });
+
+    emit!(MarketVoidedEvent {
+        market: market.key(),
         total_payout,
-        settlement_timestamp: clock.unix_timestamp,
+        settlement_timestamp,
     });
 
     Ok(())
 }
 
-/// Claim payout for winning tokens
-pub fn claim_payout(ctx: Context<ClaimPayout>) -> Result<()> {
-    let market = &ctx.accounts.market;
+/// Claim payout for winning tokens, or for either outcome's tokens once voided
+pub(crate) fn claim_payout(ctx: Context<ClaimPayout>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
     let user_token_account = &ctx.accounts.user_token_account;
 
-    // Validate market is settled
+    // Validate market is settled and not under dispute
     require!(market.status.is_settled, PredictionPumpError::MarketNotSettled);
-    
-    let winning_outcome = market.status.winning_outcome
-        .ok_or(PredictionPumpError::NoWinningOutcome)?;
+    require!(!market.status.is_disputed, PredictionPumpError::MarketDisputed);
 
     // Validate user holds winning tokens
-    let winning_token_mint = market.outcome_tokens[winning_outcome as usize];
+    let winning_token_mint = if market.status.is_voided {
+        require!(
+            market.outcome_tokens.contains(&user_token_account.mint),
+            PredictionPumpError::NotWinningTokens
+        );
+        user_token_account.mint
+    } else {
+        let winning_outcome = market.status.winning_outcome
+            .ok_or(PredictionPumpError::NoWinningOutcome)?;
+        market.outcome_tokens[winning_outcome as usize]
+    };
     require!(
         user_token_account.mint == winning_token_mint,
         PredictionPumpError::NotWinningTokens
     );
+    require!(
+        ctx.accounts.winning_token_mint.key() == winning_token_mint,
+        PredictionPumpError::NotWinningTokens
+    );
 
     let token_balance = user_token_account.amount;
     require!(token_balance > 0, PredictionPumpError::NoTokensToRedeem);
 
     // Calculate proportional payout
     let settlement_data = market.settlement_data
-        .as_ref()
+        .as_mut()
         .ok_or(PredictionPumpError::NoSettlementData)?;
 
-    // Get total supply of winning tokens to calculate proportion
-    let winning_token_mint_account = &ctx.accounts.winning_token_mint;
-    let total_winning_supply = winning_token_mint_account.supply;
-    
-    require!(total_winning_supply > 0, PredictionPumpError::NoWinningTokenSupply);
+    // Snapshot the winning supply before the first claim burns any tokens
+    if settlement_data.winning_supply == 0 {
+        settlement_data.winning_supply = ctx.accounts.winning_token_mint.supply;
+    }
 
     // Calculate user's proportional share of the payout
-    let user_payout = (settlement_data.total_payout as u128)
-        .checked_mul(token_balance as u128)
-        .ok_or(PredictionPumpError::MathOverflow)?
-        .checked_div(total_winning_supply as u128)
-        .ok_or(PredictionPumpError::MathOverflow)? as u64;
+    let user_payout = settlement_data.payout_for(token_balance)?;
 
     require!(user_payout > 0, PredictionPumpError::NoPayoutAvailable);
 
-    // Transfer SOL payout to user
-    let market_vault_info = ctx.accounts.market_vault.to_account_info();
-    let user_info = ctx.accounts.user.to_account_info();
-
-    **market_vault_info.try_borrow_mut_lamports()? = market_vault_info
-        .lamports()
-        .checked_sub(user_payout)
-        .ok_or(PredictionPumpError::InsufficientVaultFunds)?;
-
-    **user_info.try_borrow_mut_lamports()? = user_info
-        .lamports()
+    settlement_data.total_claimed = settlement_data
+        .total_claimed
         .checked_add(user_payout)
         .ok_or(PredictionPumpError::MathOverflow)?;
 
+    // Transfer the payout to the user
+    ctx.accounts.collateral.withdraw(
+        market,
+        &ctx.accounts.market_vault.to_account_info(),
+        ctx.bumps.market_vault,
+        &ctx.accounts.user.to_account_info(),
+        user_payout,
+    )?;
+
     // Burn the winning tokens
     let cpi_accounts = Burn {
         mint: ctx.accounts.winning_token_mint.to_account_info(),
@@ -146,6 +249,42 @@ pub fn claim_payout(ctx: Context<ClaimPayout>) -> Result<()> {
     Ok(())
 }
 
+/// Release the creator bond: back to the creator once a cleanly settled market
+/// is past its lockup, or to the treasury if the market was voided
+pub(crate) fn release_creator_bond(ctx: Context<ReleaseCreatorBond>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    require!(market.creator_bond > 0, PredictionPumpError::NoCreatorBond);
+    let refunded = market.status.creator_bond_refund_at(Clock::get()?.unix_timestamp)?;
+    let amount = std::mem::take(&mut market.creator_bond);
+
+    let recipient_info = if refunded {
+        ctx.accounts.creator.to_account_info()
+    } else {
+        ctx.accounts.treasury.to_account_info()
+    };
+    let vault_info = ctx.accounts.market_vault.to_account_info();
+
+    **vault_info.try_borrow_mut_lamports()? = vault_info
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(PredictionPumpError::InsufficientVaultFunds)?;
+
+    **recipient_info.try_borrow_mut_lamports()? = recipient_info
+        .lamports()
+        .checked_add(amount)
+        .ok_or(PredictionPumpError::MathOverflow)?;
+
+    emit!(CreatorBondReleasedEvent {
+        market: market.key(),
+        recipient: recipient_info.key(),
+        amount,
+        refunded,
+    });
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct SettleMarket<'info> {
     #[account(mut)]
@@ -155,35 +294,100 @@ pub struct SettleMarket<'info> {
     pub oracle_data: Account<'info, OracleData>,
 
     /// Market vault holding SOL for payouts
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump
+    )]
     /// CHECK: Market vault is validated by seeds
     pub market_vault: UncheckedAccount<'info>,
 
     /// Authority that can trigger settlement (anyone can call after resolution date)
     pub settler: Signer<'info>,
+
+    /// Collateral mint and vault token account; omitted for SOL markets
+    pub collateral: Collateral<'info>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(SettleMarket::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
 }
 
 #[derive(Accounts)]
 pub struct ClaimPayout<'info> {
+    #[account(mut)]
     pub market: Account<'info, Market>,
 
     #[account(mut)]
     pub user: Signer<'info>,
 
-    /// User's token account holding winning tokens
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
+    /// User's token account holding winning tokens (either outcome once voided)
+    #[account(mut, token::token_program = token_program)]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    /// Winning token mint to validate and burn tokens
-    #[account(mut)]
-    pub winning_token_mint: Account<'info, Mint>,
+    /// Mint of the redeemed tokens, validated and burned from
+    #[account(mut, mint::token_program = token_program)]
+    pub winning_token_mint: InterfaceAccount<'info, Mint>,
 
-    /// Market vault to transfer SOL from
-    #[account(mut)]
+    /// Market vault to pay collateral from
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump
+    )]
     /// CHECK: Market vault is validated by seeds
     pub market_vault: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// Token program owning the outcome mints, Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Collateral mint and token accounts; omitted for SOL markets
+    pub collateral: Collateral<'info>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(ClaimPayout::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseCreatorBond<'info> {
+    #[account(
+        mut,
+        has_one = creator @ PredictionPumpError::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market_vault.bump
+    )]
+    pub market_vault: Account<'info, MarketVault>,
+
+    #[account(mut)]
+    /// CHECK: Must be the market's creator; receives a refunded bond
+    pub creator: UncheckedAccount<'info>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        has_one = treasury @ PredictionPumpError::Unauthorized,
+        constraint = !protocol_config.is_paused(ReleaseCreatorBond::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(mut)]
+    /// CHECK: Must be the treasury recorded in the protocol config; receives a slashed bond
+    pub treasury: UncheckedAccount<'info>,
 }
 
 // Events for off-chain indexing and real-time updates
@@ -202,4 +406,19 @@ pub struct PayoutClaimedEvent {
     pub user: Pubkey,
     pub amount: u64,
     pub tokens_burned: u64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct MarketVoidedEvent {
+    pub market: Pubkey,
+    pub total_payout: u64,
+    pub settlement_timestamp: i64,
+}
+
+#[event]
+pub struct CreatorBondReleasedEvent {
+    pub market: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub refunded: bool, // false when slashed to the treasury
+}
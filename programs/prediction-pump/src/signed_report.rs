@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::{ed25519, Market, OracleData, OracleRegistry, PauseFlag, PredictionPumpError, ProtocolConfig};
+
+/// Record an oracle report signed off-chain by a registered provider.
+///
+/// The transaction must contain an Ed25519 program instruction immediately
+/// before this one verifying the provider's signature over the report message,
+/// so any relayer can pay for and deliver the report.
+pub(crate) fn submit_signed_report(
+    ctx: Context<SubmitSignedReport>,
+    oracle_provider: Pubkey,
+    winning_outcome: u8,
+    confidence_score: u16,
+    timestamp: i64,
+    evidence: Vec<u8>,
+) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let oracle_data = &mut ctx.accounts.oracle_data;
+    let clock = Clock::get()?;
+
+    // Validate report
+    require!(
+        ctx.accounts.oracle_registry.is_active_oracle(&oracle_provider),
+        PredictionPumpError::UnauthorizedOracle
+    );
+    // The report takes the market's only oracle data PDA, so it must come from
+    // the market's own oracle and cover the resolution date
+    require!(
+        oracle_provider == market.oracle_source,
+        PredictionPumpError::UnauthorizedOracle
+    );
+    require!(
+        timestamp >= market.resolution_date,
+        PredictionPumpError::MarketNotYetResolved
+    );
+    require!(!market.status.is_settled, PredictionPumpError::MarketAlreadySettled);
+    require!(
+        (winning_outcome as usize) < market.outcome_tokens.len(),
+        PredictionPumpError::InvalidWinningOutcome
+    );
+    require!(
+        timestamp <= clock.unix_timestamp,
+        PredictionPumpError::InvalidReportTimestamp
+    );
+
+    **oracle_data = OracleData::from_report(
+        market.key(),
+        oracle_provider,
+        winning_outcome,
+        confidence_score,
+        timestamp,
+        evidence,
+    )?;
+
+    // Verify the provider signed exactly this report
+    let instructions_sysvar = ctx.accounts.instructions_sysvar.to_account_info();
+    let current_index = load_current_index_checked(&instructions_sysvar)?;
+    require!(current_index > 0, PredictionPumpError::InvalidSignatureInstruction);
+    let signature_ix = load_instruction_at_checked(current_index as usize - 1, &instructions_sysvar)?;
+    ed25519::verify_ed25519_instruction(&signature_ix, &oracle_provider, &oracle_data.message())?;
+
+    emit!(SignedReportSubmittedEvent {
+        market: market.key(),
+        oracle_provider,
+        relayer: ctx.accounts.relayer.key(),
+        winning_outcome,
+        data_hash: oracle_data.data_hash,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SubmitSignedReport<'info> {
+    pub market: Account<'info, Market>,
+
+    #[account(
+        init,
+        payer = relayer,
+        space = OracleData::LEN,
+        seeds = [b"oracle_data", market.key().as_ref()],
+        bump
+    )]
+    pub oracle_data: Account<'info, OracleData>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(SubmitSignedReport::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Only the registry allowed by the protocol config is trusted
+    #[account(address = protocol_config.oracle_registry @ PredictionPumpError::UnauthorizedOracle)]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+
+    /// Pays for the oracle data account; does not need to be the provider
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// CHECK: Address is constrained to the instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event]
+pub struct SignedReportSubmittedEvent {
+    pub market: Pubkey,
+    pub oracle_provider: Pubkey,
+    pub relayer: Pubkey,
+    pub winning_outcome: u8,
+    pub data_hash: [u8; 32],
+}
@@ -0,0 +1,313 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program;
+
+use crate::{settlement, Market, PauseFlag, PredictionPumpError, ProtocolConfig};
+use crate::collateral::{
+    Collateral, CollateralBumps, __client_accounts_collateral, __cpi_client_accounts_collateral,
+};
+
+/// Switchboard V2 program that owns aggregator accounts
+/// (`SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f`)
+pub const SWITCHBOARD_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+    6, 136, 81, 198, 140, 104, 50, 240, 47, 165, 129, 177, 191, 73, 27, 119,
+    202, 65, 119, 107, 162, 185, 136, 181, 166, 250, 186, 142, 227, 162, 236, 144,
+]);
+
+/// Anchor discriminator of `AggregatorAccountData`
+pub const AGGREGATOR_DISCRIMINATOR: [u8; 8] = [217, 230, 65, 101, 201, 162, 27, 125];
+
+// Byte offsets into the packed `AggregatorAccountData` layout (discriminator included)
+const MIN_ORACLE_RESULTS_OFFSET: usize = 236;
+const LATEST_ROUND_OFFSET: usize = 341;
+const ROUND_NUM_SUCCESS_OFFSET: usize = LATEST_ROUND_OFFSET;
+const ROUND_OPEN_SLOT_OFFSET: usize = LATEST_ROUND_OFFSET + 9;
+const ROUND_OPEN_TIMESTAMP_OFFSET: usize = LATEST_ROUND_OFFSET + 17;
+const ROUND_RESULT_OFFSET: usize = LATEST_ROUND_OFFSET + 25;
+const MIN_AGGREGATOR_LEN: usize = ROUND_RESULT_OFFSET + 20;
+
+/// Decimal value as stored by Switchboard: `mantissa * 10^-scale`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct SwitchboardDecimal {
+    pub mantissa: i128,
+    pub scale: u32,
+}
+
+/// Latest confirmed result of a Switchboard aggregator
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AggregatorResult {
+    pub result: SwitchboardDecimal,
+    pub num_success: u32,
+    pub min_oracle_results: u32,
+    pub round_open_slot: u64,
+    pub round_open_timestamp: i64,
+}
+
+impl AggregatorResult {
+    /// Deserialize the latest confirmed round from raw aggregator account data
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        require!(data.len() >= MIN_AGGREGATOR_LEN, PredictionPumpError::InvalidAggregatorAccount);
+        require!(
+            data[..8] == AGGREGATOR_DISCRIMINATOR,
+            PredictionPumpError::InvalidAggregatorAccount
+        );
+
+        Ok(AggregatorResult {
+            result: SwitchboardDecimal {
+                mantissa: i128::from_le_bytes(read_bytes(data, ROUND_RESULT_OFFSET)),
+                scale: u32::from_le_bytes(read_bytes(data, ROUND_RESULT_OFFSET + 16)),
+            },
+            num_success: u32::from_le_bytes(read_bytes(data, ROUND_NUM_SUCCESS_OFFSET)),
+            min_oracle_results: u32::from_le_bytes(read_bytes(data, MIN_ORACLE_RESULTS_OFFSET)),
+            round_open_slot: u64::from_le_bytes(read_bytes(data, ROUND_OPEN_SLOT_OFFSET)),
+            round_open_timestamp: i64::from_le_bytes(read_bytes(data, ROUND_OPEN_TIMESTAMP_OFFSET)),
+        })
+    }
+
+    /// Check the round is recent enough and was confirmed by enough oracles
+    pub fn validate(&self, current_time: i64, max_staleness: i64, min_oracle_results: u32) -> Result<()> {
+        let age = current_time
+            .checked_sub(self.round_open_timestamp)
+            .ok_or(PredictionPumpError::MathOverflow)?;
+        require!(age >= 0 && age <= max_staleness, PredictionPumpError::StaleOracleData);
+
+        let required_results = min_oracle_results.max(self.min_oracle_results);
+        require!(
+            self.num_success >= required_results,
+            PredictionPumpError::InsufficientOracleResults
+        );
+
+        Ok(())
+    }
+
+    /// Hash of the aggregator round used as the settlement's oracle data hash
+    pub fn hash(&self, aggregator: &Pubkey) -> [u8; 32] {
+        let mut hasher = solana_program::hash::Hasher::default();
+        hasher.hash(aggregator.as_ref());
+        hasher.hash(&self.result.mantissa.to_le_bytes());
+        hasher.hash(&self.result.scale.to_le_bytes());
+        hasher.hash(&self.round_open_slot.to_le_bytes());
+        hasher.hash(&self.round_open_timestamp.to_le_bytes());
+        hasher.result().to_bytes()
+    }
+}
+
+fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> [u8; N] {
+    let mut bytes = [0u8; N];
+    bytes.copy_from_slice(&data[offset..offset + N]);
+    bytes
+}
+
+/// Settle a market from the latest result of its Switchboard aggregator
+pub(crate) fn settle_market_switchboard(ctx: Context<SettleMarketSwitchboard>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let aggregator = &ctx.accounts.aggregator;
+    let clock = Clock::get()?;
+
+    // Validate market can be settled
+    require!(!market.status.is_settled, PredictionPumpError::MarketAlreadySettled);
+    require!(
+        clock.unix_timestamp >= market.resolution_date,
+        PredictionPumpError::MarketNotYetResolved
+    );
+    // Optimistic markets settle only through finalize_proposal, which releases the bonds
+    require!(
+        market.optimistic_config.is_none(),
+        PredictionPumpError::OptimisticResolutionEnabled
+    );
+
+    // Validate aggregator account
+    require!(
+        aggregator.key() == market.oracle_source,
+        PredictionPumpError::UnauthorizedOracle
+    );
+    require!(
+        *aggregator.owner == SWITCHBOARD_PROGRAM_ID,
+        PredictionPumpError::InvalidAggregatorAccount
+    );
+
+    let condition = market
+        .resolution_condition
+        .clone()
+        .ok_or(PredictionPumpError::NoResolutionCondition)?;
+
+    let aggregator_result = {
+        let data = aggregator.try_borrow_data()?;
+        AggregatorResult::try_from_account_data(&data)?
+    };
+    aggregator_result.validate(
+        clock.unix_timestamp,
+        condition.max_staleness,
+        condition.min_oracle_results,
+    )?;
+
+    let winning_outcome = condition.evaluate(&aggregator_result.result)?;
+    let total_payout = ctx.accounts.collateral.balance(market, &ctx.accounts.market_vault.to_account_info())?;
+
+    settlement::record_settlement(
+        market,
+        winning_outcome,
+        aggregator_result.hash(&aggregator.key()),
+        total_payout,
+        clock.unix_timestamp,
+    )
+}
+
+#[derive(Accounts)]
+pub struct SettleMarketSwitchboard<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// Switchboard aggregator configured as the market's oracle source
+    /// CHECK: Owner, discriminator and key are validated in the instruction
+    pub aggregator: UncheckedAccount<'info>,
+
+    /// Market vault holding SOL for payouts
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump
+    )]
+    /// CHECK: Market vault is validated by seeds
+    pub market_vault: UncheckedAccount<'info>,
+
+    /// Authority that can trigger settlement (anyone can call after resolution date)
+    pub settler: Signer<'info>,
+
+    /// Collateral mint and vault token account; omitted for SOL markets
+    pub collateral: Collateral<'info>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(SettleMarketSwitchboard::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Comparator, ResolutionCondition};
+
+    const FIXTURE_LEN: usize = 3851; // size of a real AggregatorAccountData account
+
+    /// Build an aggregator account fixture with the given latest round values
+    fn aggregator_fixture(
+        mantissa: i128,
+        scale: u32,
+        num_success: u32,
+        min_oracle_results: u32,
+        round_open_timestamp: i64,
+    ) -> Vec<u8> {
+        let mut data = vec![0u8; FIXTURE_LEN];
+        data[..8].copy_from_slice(&AGGREGATOR_DISCRIMINATOR);
+        data[MIN_ORACLE_RESULTS_OFFSET..MIN_ORACLE_RESULTS_OFFSET + 4]
+            .copy_from_slice(&min_oracle_results.to_le_bytes());
+        data[ROUND_NUM_SUCCESS_OFFSET..ROUND_NUM_SUCCESS_OFFSET + 4]
+            .copy_from_slice(&num_success.to_le_bytes());
+        data[ROUND_OPEN_SLOT_OFFSET..ROUND_OPEN_SLOT_OFFSET + 8]
+            .copy_from_slice(&250_000_000u64.to_le_bytes());
+        data[ROUND_OPEN_TIMESTAMP_OFFSET..ROUND_OPEN_TIMESTAMP_OFFSET + 8]
+            .copy_from_slice(&round_open_timestamp.to_le_bytes());
+        data[ROUND_RESULT_OFFSET..ROUND_RESULT_OFFSET + 16].copy_from_slice(&mantissa.to_le_bytes());
+        data[ROUND_RESULT_OFFSET + 16..ROUND_RESULT_OFFSET + 20].copy_from_slice(&scale.to_le_bytes());
+        data
+    }
+
+    fn btc_above_100k() -> ResolutionCondition {
+        ResolutionCondition {
+            comparator: Comparator::GreaterThanOrEqual,
+            threshold: SwitchboardDecimal { mantissa: 100_000, scale: 0 },
+            max_staleness: 300,
+            min_oracle_results: 3,
+        }
+    }
+
+    #[test]
+    fn test_switchboard_program_id() {
+        assert_eq!(
+            SWITCHBOARD_PROGRAM_ID.to_string(),
+            "SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f"
+        );
+    }
+
+    #[test]
+    fn test_parse_aggregator_fixture() {
+        // BTC/USD = 101,234.56789
+        let data = aggregator_fixture(10_123_456_789, 5, 4, 3, 1_700_000_000);
+        let result = AggregatorResult::try_from_account_data(&data).unwrap();
+
+        assert_eq!(result.result, SwitchboardDecimal { mantissa: 10_123_456_789, scale: 5 });
+        assert_eq!(result.num_success, 4);
+        assert_eq!(result.min_oracle_results, 3);
+        assert_eq!(result.round_open_slot, 250_000_000);
+        assert_eq!(result.round_open_timestamp, 1_700_000_000);
+    }
+
+    #[test]
+    fn test_parse_rejects_wrong_discriminator() {
+        let mut data = aggregator_fixture(1, 0, 4, 3, 1_700_000_000);
+        data[0] ^= 0xff;
+        assert!(AggregatorResult::try_from_account_data(&data).is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_truncated_account() {
+        let data = aggregator_fixture(1, 0, 4, 3, 1_700_000_000);
+        assert!(AggregatorResult::try_from_account_data(&data[..MIN_AGGREGATOR_LEN - 1]).is_err());
+    }
+
+    #[test]
+    fn test_validate_staleness() {
+        let data = aggregator_fixture(1, 0, 4, 3, 1_700_000_000);
+        let result = AggregatorResult::try_from_account_data(&data).unwrap();
+
+        assert!(result.validate(1_700_000_300, 300, 3).is_ok());
+        assert!(result.validate(1_700_000_301, 300, 3).is_err());
+        // Rounds opened in the future are rejected as well
+        assert!(result.validate(1_699_999_999, 300, 3).is_err());
+    }
+
+    #[test]
+    fn test_validate_min_oracle_results() {
+        let data = aggregator_fixture(1, 0, 2, 1, 1_700_000_000);
+        let result = AggregatorResult::try_from_account_data(&data).unwrap();
+
+        // Market requirement is stricter than the aggregator's
+        assert!(result.validate(1_700_000_000, 300, 3).is_err());
+        assert!(result.validate(1_700_000_000, 300, 2).is_ok());
+
+        // Aggregator requirement is stricter than the market's
+        let data = aggregator_fixture(1, 0, 2, 5, 1_700_000_000);
+        let result = AggregatorResult::try_from_account_data(&data).unwrap();
+        assert!(result.validate(1_700_000_000, 300, 1).is_err());
+    }
+
+    #[test]
+    fn test_fixture_maps_to_outcome() {
+        let condition = btc_above_100k();
+
+        let above = aggregator_fixture(10_123_456_789, 5, 4, 3, 1_700_000_000);
+        let result = AggregatorResult::try_from_account_data(&above).unwrap();
+        assert_eq!(condition.evaluate(&result.result).unwrap(), 0);
+
+        let below = aggregator_fixture(9_999_999_999, 5, 4, 3, 1_700_000_000);
+        let result = AggregatorResult::try_from_account_data(&below).unwrap();
+        assert_eq!(condition.evaluate(&result.result).unwrap(), 1);
+
+        let exact = aggregator_fixture(10_000_000_000, 5, 4, 3, 1_700_000_000);
+        let result = AggregatorResult::try_from_account_data(&exact).unwrap();
+        assert_eq!(condition.evaluate(&result.result).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_aggregator_hash_changes_with_round() {
+        let aggregator = Pubkey::new_unique();
+        let first = AggregatorResult::try_from_account_data(&aggregator_fixture(1, 0, 4, 3, 1_700_000_000)).unwrap();
+        let second = AggregatorResult::try_from_account_data(&aggregator_fixture(1, 0, 4, 3, 1_700_000_060)).unwrap();
+
+        assert_eq!(first.hash(&aggregator), first.hash(&aggregator));
+        assert_ne!(first.hash(&aggregator), second.hash(&aggregator));
+    }
+}
@@ -1,7 +1,30 @@
 #[cfg(test)]
 mod tests {
     use crate::{SettlementData, MarketStatus, OracleData, Dispute, DisputeVote, DisputeOutcome, DisputeResolution, BondingCurveParams, BondingCurve};
+    use crate::{VOTER_SLASH_BPS, OptimisticConfig, Proposal, OracleRegistry, OracleProvider, OracleType};
+    use crate::{RELIABILITY_PENALTY, RELIABILITY_REWARD, MIN_RELIABILITY_SCORE, MAX_ORACLE_EVIDENCE_LEN};
+    use crate::{DisputeConfig, DisputeConfigParams, UPHOLD_ORIGINAL_VOTE, VOID_MARKET_VOTE, MAX_DISPUTE_APPEALS};
+    use crate::{CREATOR_BOND_LOCKUP, DISPUTE_WINDOW};
+    use crate::{DisputeEvidence, MAX_DISPUTE_EVIDENCE, MAX_EVIDENCE_URI_LEN};
+    use crate::{FeeBounds, FeeSchedule, MarketFees, ProtocolConfig, ProtocolConfigParams};
+    use crate::{Market, NATIVE_SOL_DECIMALS};
+    use crate::{CreatorRewards, CreatorStake, LiquidityPool, LpPosition, LP_LOCKUP};
+    use crate::{DynamicFee, DynamicFeeParams};
+    use crate::outcome_mint_name;
+    use crate::{PauseFlag, PAUSE_TRADING, PAUSE_SETTLEMENT, PAUSE_CLAIMS, PAUSE_DISPUTES, PAUSE_ALL};
+    use crate::collateral::Collateral;
     use anchor_lang::prelude::*;
+    use anchor_lang::system_program;
+    use anchor_lang::solana_program::entrypoint::{ProgramResult, SUCCESS};
+    use anchor_lang::solana_program::instruction::Instruction;
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+    use anchor_spl::associated_token::get_associated_token_address;
+    use anchor_spl::token::TokenAccount;
+    use spl_token::instruction::TokenInstruction;
+    use spl_token::state::AccountState;
+    use std::cell::RefCell;
+    use std::sync::Once;
 
     #[test]
     fn test_settlement_data_creation() {
@@ -10,6 +33,8 @@ mod tests {
             settlement_timestamp: 1691234567,
             oracle_data_hash: [1u8; 32],
             total_payout: 1000000,
+            winning_supply: 0,
+            total_claimed: 0,
         };
 
         assert_eq!(settlement_data.winning_outcome, 1);
@@ -21,6 +46,8 @@ mod tests {
         let mut status = MarketStatus {
             is_active: true,
             is_settled: false,
+            is_disputed: false,
+            is_voided: false,
             winning_outcome: None,
             settlement_timestamp: None,
         };
@@ -48,8 +75,10 @@ mod tests {
             winning_outcome: 1,
             confidence_score: 9500,
             timestamp: 1691234567,
+            evidence: Vec::new(),
             data_hash: [1u8; 32],
             is_disputed: false,
+            is_ruled: false,
         };
 
         assert_eq!(oracle_data.winning_outcome, 1);
@@ -74,8 +103,28 @@ mod tests {
             stake_amount,
             submission_time: 1691234567,
             voting_end_time: 1691234567 + (7 * 24 * 60 * 60), // 7 days later
-            votes: Vec::new(),
+            reveal_end_time: 1691234567 + (9 * 24 * 60 * 60), // 2 day reveal
+            quorum: 0,
+            supermajority_bps: 5000,
+            voting_period: 7 * 24 * 60 * 60,
+            reveal_period: 2 * 24 * 60 * 60,
+            appeal_period: 24 * 60 * 60,
+            max_appeals: 0,
+            round: 0,
+            round_stake: 1_000_000,
+            appeal_deadline: 0,
+            past_rounds: Vec::new(),
+            oracle_bond: 0,
+            bond_pending: false,
+            evidence_count: 0,
+            voter_count: 0,
+            committed_votes: 0,
+            outcome_0_votes: 0,
+            outcome_1_votes: 0,
+            uphold_votes: 0,
+            void_votes: 0,
             is_resolved: false,
+            is_finalized: false,
             resolution: None,
         };
 
@@ -83,7 +132,8 @@ mod tests {
         assert_eq!(dispute.reason, reason);
         assert_eq!(dispute.stake_amount, stake_amount);
         assert!(!dispute.is_resolved);
-        assert!(dispute.votes.is_empty());
+        assert_eq!(dispute.voter_count, 0);
+        assert_eq!(dispute.committed_votes, 0);
     }
 
     #[test]
@@ -93,14 +143,19 @@ mod tests {
         let weight = 1000u64;
 
         let vote = DisputeVote {
+            dispute: Pubkey::new_unique(),
             voter,
-            outcome,
+            round: 0,
+            commitment: DisputeVote::compute_commitment(outcome, &[9u8; 32], &voter),
+            outcome: Some(outcome),
             weight,
             timestamp: 1691234567,
+            stake_withdrawn: false,
+            bump: 255,
         };
 
         assert_eq!(vote.voter, voter);
-        assert_eq!(vote.outcome, outcome);
+        assert_eq!(vote.outcome, Some(outcome));
         assert_eq!(vote.weight, weight);
     }
 
@@ -118,64 +173,48 @@ mod tests {
             stake_amount: 1_000_000,
             submission_time: 1691234567,
             voting_end_time: 1691234567 + (7 * 24 * 60 * 60),
-            votes: Vec::new(),
+            reveal_end_time: 1691234567 + (9 * 24 * 60 * 60),
+            quorum: 0,
+            supermajority_bps: 5000,
+            voting_period: 7 * 24 * 60 * 60,
+            reveal_period: 2 * 24 * 60 * 60,
+            appeal_period: 24 * 60 * 60,
+            max_appeals: 0,
+            round: 0,
+            round_stake: 1_000_000,
+            appeal_deadline: 0,
+            past_rounds: Vec::new(),
+            oracle_bond: 0,
+            bond_pending: false,
+            evidence_count: 0,
+            voter_count: 0,
+            committed_votes: 0,
+            outcome_0_votes: 0,
+            outcome_1_votes: 0,
+            uphold_votes: 0,
+            void_votes: 0,
             is_resolved: false,
+            is_finalized: false,
             resolution: None,
         };
 
         // Add votes for different outcomes
-        let voter1 = Pubkey::new_unique();
-        let voter2 = Pubkey::new_unique();
-        let voter3 = Pubkey::new_unique();
-
-        // Vote for outcome 0 (2000 weight)
-        dispute.votes.push(DisputeVote {
-            voter: voter1,
-            outcome: 0,
-            weight: 2000,
-            timestamp: 1691234567,
-        });
-
-        // Vote for outcome 1 (1500 weight)
-        dispute.votes.push(DisputeVote {
-            voter: voter2,
-            outcome: 1,
-            weight: 1500,
-            timestamp: 1691234567,
-        });
-
-        // Vote to uphold original (500 weight)
-        dispute.votes.push(DisputeVote {
-            voter: voter3,
-            outcome: 255, // Special value for uphold
-            weight: 500,
-            timestamp: 1691234567,
-        });
-
-        // Test vote counting logic manually (since calculate_resolution requires Clock sysvar)
-        let mut outcome_0_votes = 0u64;
-        let mut outcome_1_votes = 0u64;
-        let mut uphold_votes = 0u64;
-        let mut total_votes = 0u64;
-
-        for vote in &dispute.votes {
-            total_votes += vote.weight;
-            match vote.outcome {
-                0 => outcome_0_votes += vote.weight,
-                1 => outcome_1_votes += vote.weight,
-                255 => uphold_votes += vote.weight,
-                _ => {}
-            }
+        for (outcome, weight) in [(0u8, 2000u64), (1, 1500), (255, 500)] {
+            dispute.record_commit(weight).unwrap();
+            dispute.record_reveal(outcome, weight).unwrap();
         }
 
         // Verify vote counting
-        assert_eq!(total_votes, 4000);
-        assert_eq!(outcome_0_votes, 2000);
-        assert_eq!(outcome_1_votes, 1500);
-        assert_eq!(uphold_votes, 500);
-
-        // Verify outcome 0 wins
-        assert!(outcome_0_votes >= outcome_1_votes && outcome_0_votes >= uphold_votes);
+        assert_eq!(dispute.voter_count, 3);
+        assert_eq!(dispute.revealed_votes().unwrap(), 4000);
+        assert_eq!(dispute.outcome_0_votes, 2000);
+        assert_eq!(dispute.outcome_1_votes, 1500);
+        assert_eq!(dispute.uphold_votes, 500);
+
+        let resolution = dispute.calculate_resolution_at(1691234567).unwrap();
+        assert_eq!(resolution.outcome, DisputeOutcome::OverrideOutcome(0));
+        assert_eq!(resolution.total_votes, 4000);
+        assert_eq!(resolution.winning_votes, 2000);
     }
 
     #[test]
@@ -192,52 +231,45 @@ mod tests {
             stake_amount: 1_000_000,
             submission_time: 1691234567,
             voting_end_time: 1691234567 + (7 * 24 * 60 * 60),
-            votes: Vec::new(),
+            reveal_end_time: 1691234567 + (9 * 24 * 60 * 60),
+            quorum: 0,
+            supermajority_bps: 5000,
+            voting_period: 7 * 24 * 60 * 60,
+            reveal_period: 2 * 24 * 60 * 60,
+            appeal_period: 24 * 60 * 60,
+            max_appeals: 0,
+            round: 0,
+            round_stake: 1_000_000,
+            appeal_deadline: 0,
+            past_rounds: Vec::new(),
+            oracle_bond: 0,
+            bond_pending: false,
+            evidence_count: 0,
+            voter_count: 0,
+            committed_votes: 0,
+            outcome_0_votes: 0,
+            outcome_1_votes: 0,
+            uphold_votes: 0,
+            void_votes: 0,
             is_resolved: false,
+            is_finalized: false,
             resolution: None,
         };
 
         // Add votes where "uphold original" wins
-        let voter1 = Pubkey::new_unique();
-        let voter2 = Pubkey::new_unique();
-
-        // Vote for outcome 0 (1000 weight)
-        dispute.votes.push(DisputeVote {
-            voter: voter1,
-            outcome: 0,
-            weight: 1000,
-            timestamp: 1691234567,
-        });
-
-        // Vote to uphold original (2000 weight)
-        dispute.votes.push(DisputeVote {
-            voter: voter2,
-            outcome: 255, // Special value for uphold
-            weight: 2000,
-            timestamp: 1691234567,
-        });
-
-        // Test vote counting logic manually (since calculate_resolution requires Clock sysvar)
-        let mut outcome_0_votes = 0u64;
-        let mut uphold_votes = 0u64;
-        let mut total_votes = 0u64;
-
-        for vote in &dispute.votes {
-            total_votes += vote.weight;
-            match vote.outcome {
-                0 => outcome_0_votes += vote.weight,
-                255 => uphold_votes += vote.weight,
-                _ => {}
-            }
+        for (outcome, weight) in [(0u8, 1000u64), (255, 2000)] {
+            dispute.record_commit(weight).unwrap();
+            dispute.record_reveal(outcome, weight).unwrap();
         }
 
         // Verify vote counting
-        assert_eq!(total_votes, 3000);
-        assert_eq!(outcome_0_votes, 1000);
-        assert_eq!(uphold_votes, 2000);
+        assert_eq!(dispute.revealed_votes().unwrap(), 3000);
+        assert_eq!(dispute.outcome_0_votes, 1000);
+        assert_eq!(dispute.uphold_votes, 2000);
 
-        // Verify uphold wins
-        assert!(uphold_votes >= outcome_0_votes);
+        let resolution = dispute.calculate_resolution_at(1691234567).unwrap();
+        assert_eq!(resolution.outcome, DisputeOutcome::UpholdOriginal);
+        assert_eq!(resolution.winning_votes, 2000);
     }
 
     #[test]
@@ -251,8 +283,10 @@ mod tests {
             winning_outcome: 1,
             confidence_score: 9500,
             timestamp: 1691234567,
+            evidence: Vec::new(),
             data_hash: [1u8; 32],
             is_disputed: false,
+            is_ruled: false,
         };
 
         // Test disputing oracle data
@@ -261,6 +295,34 @@ mod tests {
         assert!(oracle_data.is_disputed);
     }
 
+    #[test]
+    fn test_ruled_report_cannot_be_disputed_again() {
+        let mut oracle_data = create_test_report(Vec::new());
+        oracle_data.dispute().unwrap();
+        assert!(oracle_data.dispute().is_err());
+
+        // Finalizing the dispute clears the flag but the ruling stays binding
+        oracle_data.is_disputed = false;
+        oracle_data.is_ruled = true;
+        assert!(oracle_data.dispute().is_err());
+    }
+
+    #[test]
+    fn test_dispute_window_closes_after_settlement() {
+        let settled_at = 1691234567;
+        let mut status = settled_status(settled_at);
+
+        assert!(status.check_dispute_window_at(settled_at + DISPUTE_WINDOW - 1).is_ok());
+        assert!(status.check_dispute_window_at(settled_at + DISPUTE_WINDOW).is_err());
+
+        // Unsettled markets stay open to disputes; voided ones never are
+        status.is_settled = false;
+        status.settlement_timestamp = None;
+        assert!(status.check_dispute_window_at(settled_at + 10 * DISPUTE_WINDOW).is_ok());
+        status.is_voided = true;
+        assert!(status.check_dispute_window_at(settled_at).is_err());
+    }
+
     // ============================================================================
     // BONDING CURVE UNIT TESTS - Task 6.1
     // ============================================================================
@@ -678,4 +740,1931 @@ mod tests {
             }
         }
     }
-}
\ No newline at end of file
+
+    // ============================================================================
+    // OPTIMISTIC ORACLE UNIT TESTS
+    // ============================================================================
+
+    fn create_test_proposal() -> Proposal {
+        Proposal::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            1,
+            50_000_000, // 0.05 SOL bond
+            1691234567,
+            2 * 60 * 60, // 2 hour liveness
+            255,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_optimistic_config_validation() {
+        assert!(OptimisticConfig::new(50_000_000, 7200).is_ok());
+        assert!(OptimisticConfig::new(0, 7200).is_err());
+        assert!(OptimisticConfig::new(50_000_000, 0).is_err());
+    }
+
+    #[test]
+    fn test_proposal_liveness_window() {
+        let proposal = create_test_proposal();
+
+        assert_eq!(proposal.liveness_end, 1691234567 + 7200);
+        assert!(proposal.is_open(1691234567));
+        assert!(proposal.is_open(proposal.liveness_end - 1));
+        assert!(!proposal.is_open(proposal.liveness_end));
+    }
+
+    #[test]
+    fn test_undisputed_proposal_returns_bond_to_proposer() {
+        let proposal = create_test_proposal();
+
+        assert_eq!(proposal.escrowed_bond().unwrap(), 50_000_000);
+        assert_eq!(proposal.bond_winner(proposal.proposed_outcome), proposal.proposer);
+    }
+
+    #[test]
+    fn test_disputed_proposal_bond_goes_to_correct_side() {
+        let mut proposal = create_test_proposal();
+        let disputer = Pubkey::new_unique();
+
+        proposal.record_dispute(disputer, Pubkey::new_unique()).unwrap();
+
+        // Disputed proposals are closed to further disputes and escrow both bonds
+        assert!(!proposal.is_open(1691234567));
+        assert_eq!(proposal.escrowed_bond().unwrap(), 100_000_000);
+
+        // Voting upheld the proposal
+        assert_eq!(proposal.bond_winner(1), proposal.proposer);
+        // Voting overturned the proposal
+        assert_eq!(proposal.bond_winner(0), disputer);
+    }
+
+    #[test]
+    fn test_proposal_cannot_be_disputed_twice() {
+        let mut proposal = create_test_proposal();
+
+        assert!(proposal.record_dispute(Pubkey::new_unique(), Pubkey::new_unique()).is_ok());
+        assert!(proposal.record_dispute(Pubkey::new_unique(), Pubkey::new_unique()).is_err());
+    }
+
+    // ============================================================================
+    // ORACLE RELIABILITY UNIT TESTS
+    // ============================================================================
+
+    #[test]
+    fn test_upheld_report_raises_reliability() {
+        let mut provider = OracleProvider::new(Pubkey::new_unique(), OracleType::Custom, 8000).unwrap();
+
+        provider.record_dispute_outcome(true);
+        assert_eq!(provider.reliability_score, 8000 + RELIABILITY_REWARD);
+        assert!(provider.is_active);
+
+        // Score is capped at 10000
+        provider.reliability_score = 9900;
+        provider.record_dispute_outcome(true);
+        assert_eq!(provider.reliability_score, 10000);
+    }
+
+    #[test]
+    fn test_overridden_report_lowers_reliability_and_deactivates() {
+        let mut provider = OracleProvider::new(Pubkey::new_unique(), OracleType::Switchboard, 7000).unwrap();
+
+        provider.record_dispute_outcome(false);
+        assert_eq!(provider.reliability_score, 7000 - RELIABILITY_PENALTY);
+        assert!(provider.is_active);
+
+        provider.record_dispute_outcome(false);
+        provider.record_dispute_outcome(false);
+        assert!(provider.reliability_score < MIN_RELIABILITY_SCORE);
+        assert!(!provider.is_active);
+
+        // Upheld reports do not reactivate a provider below the minimum
+        provider.record_dispute_outcome(true);
+        assert!(!provider.is_active);
+    }
+
+    #[test]
+    fn test_dispute_outcomes_change_fallback_selection() {
+        let mut registry = OracleRegistry::new(Pubkey::new_unique(), 1).unwrap();
+        let primary = Pubkey::new_unique();
+        let oracle_a = Pubkey::new_unique();
+        let oracle_b = Pubkey::new_unique();
+
+        registry.add_oracle(OracleProvider::new(primary, OracleType::Custom, 9000).unwrap()).unwrap();
+        registry.add_oracle(OracleProvider::new(oracle_a, OracleType::Pyth, 9000).unwrap()).unwrap();
+        registry.add_oracle(OracleProvider::new(oracle_b, OracleType::Switchboard, 8500).unwrap()).unwrap();
+
+        assert_eq!(registry.select_fallback_oracle(primary).unwrap().provider_id, oracle_a);
+
+        // oracle_a gets overridden once and falls behind oracle_b
+        let updated = registry.record_dispute_outcome(oracle_a, false).unwrap();
+        assert_eq!(updated.reliability_score, 9000 - RELIABILITY_PENALTY);
+        assert_eq!(registry.select_fallback_oracle(primary).unwrap().provider_id, oracle_b);
+
+        // Repeated overrides deactivate oracle_a entirely
+        for _ in 0..4 {
+            registry.record_dispute_outcome(oracle_a, false);
+        }
+        assert_eq!(registry.get_active_oracles().len(), 2);
+    }
+
+    #[test]
+    fn test_dispute_outcome_for_unregistered_reporter() {
+        let mut registry = OracleRegistry::new(Pubkey::new_unique(), 1).unwrap();
+        assert!(registry.record_dispute_outcome(Pubkey::new_unique(), false).is_none());
+    }
+
+    // ============================================================================
+    // ORACLE DATA INTEGRITY UNIT TESTS
+    // ============================================================================
+
+    fn create_test_report(evidence: Vec<u8>) -> OracleData {
+        OracleData::from_report(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            0,
+            9500,
+            1691234567,
+            evidence,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_oracle_data_integrity_roundtrip() {
+        let oracle_data = create_test_report(Vec::new());
+        assert!(oracle_data.validate_data_integrity().unwrap());
+
+        // Price value as evidence (e.g. BTC/USD in cents)
+        let oracle_data = create_test_report(10_123_456u64.to_le_bytes().to_vec());
+        assert!(oracle_data.validate_data_integrity().unwrap());
+    }
+
+    #[test]
+    fn test_oracle_data_hash_covers_timestamp() {
+        let mut oracle_data = create_test_report(Vec::new());
+        oracle_data.timestamp += 1;
+        assert!(!oracle_data.validate_data_integrity().unwrap());
+    }
+
+    #[test]
+    fn test_oracle_data_hash_covers_evidence() {
+        // URL hash as evidence
+        let mut oracle_data = create_test_report(vec![7u8; 32]);
+        oracle_data.evidence[0] = 8;
+        assert!(!oracle_data.validate_data_integrity().unwrap());
+
+        let mut oracle_data = create_test_report(vec![7u8; 32]);
+        oracle_data.evidence.clear();
+        assert!(!oracle_data.validate_data_integrity().unwrap());
+    }
+
+    #[test]
+    fn test_oracle_data_evidence_length_limit() {
+        assert!(OracleData::from_report(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            0,
+            9500,
+            1691234567,
+            vec![0u8; MAX_ORACLE_EVIDENCE_LEN],
+        )
+        .is_ok());
+
+        assert!(OracleData::from_report(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            0,
+            9500,
+            1691234567,
+            vec![0u8; MAX_ORACLE_EVIDENCE_LEN + 1],
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_oracle_report_message_is_signed_payload() {
+        let oracle_data = create_test_report(vec![1, 2, 3]);
+        let message = oracle_data.message();
+
+        assert!(message.starts_with(OracleData::REPORT_DOMAIN));
+        assert!(message.ends_with(&[3u8, 0, 1, 2, 3])); // evidence length prefix + evidence
+        assert_eq!(
+            anchor_lang::solana_program::hash::hash(&message).to_bytes(),
+            oracle_data.data_hash
+        );
+    }
+
+    #[test]
+    fn test_signed_reports_require_active_registered_provider() {
+        let mut registry = OracleRegistry::new(Pubkey::new_unique(), 1).unwrap();
+        let provider = Pubkey::new_unique();
+
+        assert!(!registry.is_active_oracle(&provider));
+
+        registry.add_oracle(OracleProvider::new(provider, OracleType::Custom, 9000).unwrap()).unwrap();
+        assert!(registry.is_active_oracle(&provider));
+
+        registry.oracles[0].deactivate().unwrap();
+        assert!(!registry.is_active_oracle(&provider));
+    }
+
+    // ============================================================================
+    // LOCKED-STAKE VOTING UNIT TESTS
+    // ============================================================================
+
+    fn create_vote(dispute: &Dispute, outcome: Option<u8>, weight: u64) -> DisputeVote {
+        let voter = Pubkey::new_unique();
+        DisputeVote {
+            dispute: Pubkey::new_unique(),
+            voter,
+            round: 0,
+            commitment: DisputeVote::compute_commitment(outcome.unwrap_or(0), &[0u8; 32], &voter),
+            outcome,
+            weight,
+            timestamp: dispute.submission_time,
+            stake_withdrawn: false,
+            bump: 255,
+        }
+    }
+
+    /// Everything a voter receives: returned stake plus reward share
+    fn vote_payout(dispute: &Dispute, vote: &DisputeVote) -> Result<u64> {
+        Ok(dispute.vote_principal(vote)? + dispute.vote_reward(vote)?)
+    }
+
+    fn create_empty_dispute() -> Dispute {
+        Dispute {
+            market: Pubkey::new_unique(),
+            oracle_data: Pubkey::new_unique(),
+            disputer: Pubkey::new_unique(),
+            reason: "Test dispute".to_string(),
+            stake_amount: 1_000_000,
+            submission_time: 1691234567,
+            voting_end_time: 1691234567 + (7 * 24 * 60 * 60),
+            reveal_end_time: 1691234567 + (9 * 24 * 60 * 60),
+            quorum: 0,
+            supermajority_bps: 5000,
+            voting_period: 7 * 24 * 60 * 60,
+            reveal_period: 2 * 24 * 60 * 60,
+            appeal_period: 24 * 60 * 60,
+            max_appeals: 0,
+            round: 0,
+            round_stake: 1_000_000,
+            appeal_deadline: 0,
+            past_rounds: Vec::new(),
+            oracle_bond: 0,
+            bond_pending: false,
+            evidence_count: 0,
+            voter_count: 0,
+            committed_votes: 0,
+            outcome_0_votes: 0,
+            outcome_1_votes: 0,
+            uphold_votes: 0,
+            void_votes: 0,
+            is_resolved: false,
+            is_finalized: false,
+            resolution: None,
+        }
+    }
+
+    /// Commit and reveal every vote, then resolve with the given outcome
+    fn create_resolved_dispute(votes: Vec<(u8, u64)>, outcome: DisputeOutcome) -> (Dispute, Vec<DisputeVote>) {
+        let mut dispute = create_empty_dispute();
+        let votes: Vec<DisputeVote> = votes
+            .into_iter()
+            .map(|(outcome, weight)| create_vote(&dispute, Some(outcome), weight))
+            .collect();
+        for vote in &votes {
+            dispute.record_commit(vote.weight).unwrap();
+            dispute.record_reveal(vote.outcome.unwrap(), vote.weight).unwrap();
+        }
+
+        let resolution = DisputeResolution {
+            outcome: outcome.clone(),
+            quorum_reached: true,
+            decisive: true,
+            total_votes: dispute.revealed_votes().unwrap(),
+            winning_votes: votes
+                .iter()
+                .filter(|v| match outcome {
+                    DisputeOutcome::UpholdOriginal => v.outcome == Some(255),
+                    DisputeOutcome::OverrideOutcome(o) => v.outcome == Some(o),
+                    DisputeOutcome::VoidMarket => v.outcome == Some(VOID_MARKET_VOTE),
+                })
+                .map(|v| v.weight)
+                .sum(),
+            resolution_timestamp: dispute.voting_end_time,
+        };
+        dispute.resolve(resolution).unwrap();
+
+        (dispute, votes)
+    }
+
+    #[test]
+    fn test_vote_outcome_validation() {
+        assert!(Dispute::is_valid_vote_outcome(0));
+        assert!(Dispute::is_valid_vote_outcome(1));
+        assert!(Dispute::is_valid_vote_outcome(255));
+        assert!(!Dispute::is_valid_vote_outcome(2));
+    }
+
+    #[test]
+    fn test_losing_voters_are_slashed() {
+        let (dispute, votes) = create_resolved_dispute(
+            vec![(0, 3_000_000), (255, 1_000_000)],
+            DisputeOutcome::OverrideOutcome(0),
+        );
+
+        let loser_payout = vote_payout(&dispute, &votes[1]).unwrap();
+        let slash = 1_000_000 * VOTER_SLASH_BPS as u64 / 10000;
+        assert_eq!(loser_payout, 1_000_000 - slash);
+    }
+
+    #[test]
+    fn test_winning_voters_share_slashed_stake() {
+        let (dispute, votes) = create_resolved_dispute(
+            vec![(255, 2_000_000), (255, 6_000_000), (1, 4_000_000)],
+            DisputeOutcome::UpholdOriginal,
+        );
+
+        let slashed = 4_000_000 * VOTER_SLASH_BPS as u64 / 10000;
+        let small_winner = vote_payout(&dispute, &votes[0]).unwrap();
+        let large_winner = vote_payout(&dispute, &votes[1]).unwrap();
+        let loser = vote_payout(&dispute, &votes[2]).unwrap();
+
+        assert_eq!(small_winner, 2_000_000 + slashed / 4);
+        assert_eq!(large_winner, 6_000_000 + slashed * 3 / 4);
+
+        // Payouts never exceed the locked total
+        assert!(small_winner + large_winner + loser <= 12_000_000);
+    }
+
+    #[test]
+    fn test_unanimous_vote_returns_full_stake() {
+        let (dispute, votes) = create_resolved_dispute(
+            vec![(1, 1_000_000), (1, 2_000_000)],
+            DisputeOutcome::OverrideOutcome(1),
+        );
+
+        assert_eq!(vote_payout(&dispute, &votes[0]).unwrap(), 1_000_000);
+        assert_eq!(vote_payout(&dispute, &votes[1]).unwrap(), 2_000_000);
+    }
+
+    #[test]
+    fn test_vote_payout_requires_resolution() {
+        let (mut dispute, votes) = create_resolved_dispute(vec![(0, 1_000_000)], DisputeOutcome::OverrideOutcome(0));
+        dispute.resolution = None;
+        assert!(vote_payout(&dispute, &votes[0]).is_err());
+    }
+
+    // ============================================================================
+    // COMMIT-REVEAL VOTING UNIT TESTS
+    // ============================================================================
+
+    fn create_committed_votes(
+        dispute: &mut Dispute,
+        votes: &[(Pubkey, u8, [u8; 32], u64)],
+    ) -> Vec<DisputeVote> {
+        votes
+            .iter()
+            .map(|(voter, outcome, salt, weight)| {
+                dispute.record_commit(*weight).unwrap();
+                DisputeVote {
+                    dispute: Pubkey::new_unique(),
+                    voter: *voter,
+                    round: 0,
+                    commitment: DisputeVote::compute_commitment(*outcome, salt, voter),
+                    outcome: None,
+                    weight: *weight,
+                    timestamp: dispute.submission_time,
+                    stake_withdrawn: false,
+                    bump: 255,
+                }
+            })
+            .collect()
+    }
+
+    fn reveal(dispute: &mut Dispute, vote: &mut DisputeVote, outcome: u8, salt: &[u8; 32]) {
+        vote.reveal(outcome, salt).unwrap();
+        dispute.record_reveal(outcome, vote.weight).unwrap();
+    }
+
+    #[test]
+    fn test_commitment_binds_outcome_salt_and_voter() {
+        let voter = Pubkey::new_unique();
+        let salt = [3u8; 32];
+        let commitment = DisputeVote::compute_commitment(1, &salt, &voter);
+
+        assert_eq!(commitment, DisputeVote::compute_commitment(1, &salt, &voter));
+        assert_ne!(commitment, DisputeVote::compute_commitment(0, &salt, &voter));
+        assert_ne!(commitment, DisputeVote::compute_commitment(1, &[4u8; 32], &voter));
+        assert_ne!(commitment, DisputeVote::compute_commitment(1, &salt, &Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_reveal_vote() {
+        let voter = Pubkey::new_unique();
+        let salt = [5u8; 32];
+        let mut dispute = create_empty_dispute();
+        let mut votes = create_committed_votes(&mut dispute, &[(voter, 0, salt, 1_000_000)]);
+        let vote = &mut votes[0];
+
+        // Wrong outcome or salt does not open the commitment
+        assert!(vote.reveal(1, &salt).is_err());
+        assert!(vote.reveal(0, &[6u8; 32]).is_err());
+
+        assert!(vote.reveal(0, &salt).is_ok());
+        assert_eq!(vote.outcome, Some(0));
+
+        // Cannot reveal twice
+        assert!(vote.reveal(0, &salt).is_err());
+    }
+
+    #[test]
+    fn test_reveal_rejects_invalid_outcome() {
+        let voter = Pubkey::new_unique();
+        let salt = [5u8; 32];
+        let mut dispute = create_empty_dispute();
+        let mut votes = create_committed_votes(&mut dispute, &[(voter, 7, salt, 1_000_000)]);
+
+        assert!(votes[0].reveal(7, &salt).is_err());
+        assert!(dispute.record_reveal(7, 1_000_000).is_err());
+    }
+
+    #[test]
+    fn test_resolution_counts_only_revealed_votes() {
+        let (voter1, voter2, voter3) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let salt = [1u8; 32];
+        let mut dispute = create_empty_dispute();
+        let mut votes = create_committed_votes(&mut dispute, &[
+            (voter1, 0, salt, 1_000_000),
+            (voter2, 255, salt, 3_000_000),
+            (voter3, 255, salt, 5_000_000),
+        ]);
+        assert_eq!(dispute.voter_count, 3);
+        assert_eq!(dispute.committed_votes, 9_000_000);
+
+        // Nothing revealed yet: no quorum, so the original outcome stands
+        let resolution = dispute.calculate_resolution_at(1691234567).unwrap();
+        assert!(!resolution.quorum_reached);
+        assert_eq!(resolution.outcome, DisputeOutcome::UpholdOriginal);
+
+        // The heaviest commitment never gets revealed
+        reveal(&mut dispute, &mut votes[0], 0, &salt);
+        reveal(&mut dispute, &mut votes[1], 255, &salt);
+
+        let resolution = dispute.calculate_resolution_at(1691234567).unwrap();
+        assert_eq!(resolution.outcome, DisputeOutcome::UpholdOriginal);
+        assert_eq!(resolution.total_votes, 4_000_000);
+        assert_eq!(resolution.winning_votes, 3_000_000);
+    }
+
+    #[test]
+    fn test_unrevealed_stake_is_forfeited_to_winners() {
+        let (voter1, voter2, voter3) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let salt = [1u8; 32];
+        let mut dispute = create_empty_dispute();
+        let mut votes = create_committed_votes(&mut dispute, &[
+            (voter1, 1, salt, 2_000_000),
+            (voter2, 255, salt, 1_000_000),
+            (voter3, 1, salt, 4_000_000),
+        ]);
+        reveal(&mut dispute, &mut votes[0], 1, &salt);
+        reveal(&mut dispute, &mut votes[1], 255, &salt);
+        let resolution = dispute.calculate_resolution_at(1691234567).unwrap();
+        dispute.resolve(resolution).unwrap();
+
+        // The overturning disputer's 1_000_000 stake shares the pool with the winner
+        let slashed = 1_000_000 * VOTER_SLASH_BPS as u64 / 10000;
+        let pool = slashed + 4_000_000;
+        assert_eq!(dispute.reward_pool(0).unwrap(), (pool, 3_000_000));
+        assert_eq!(vote_payout(&dispute, &votes[0]).unwrap(), 2_000_000 + pool * 2 / 3);
+        assert_eq!(vote_payout(&dispute, &votes[1]).unwrap(), 1_000_000 - slashed);
+        assert_eq!(vote_payout(&dispute, &votes[2]).unwrap(), 0);
+        assert_eq!(dispute.disputer_reward().unwrap(), pool / 3);
+    }
+
+    // ============================================================================
+    // DISPUTE CONFIG UNIT TESTS
+    // ============================================================================
+
+    fn record_revealed_votes(dispute: &mut Dispute, votes: &[(u8, u64)]) {
+        for (outcome, weight) in votes {
+            dispute.record_commit(*weight).unwrap();
+            dispute.record_reveal(*outcome, *weight).unwrap();
+        }
+    }
+
+    fn dispute_config_params() -> DisputeConfigParams {
+        let day = 24 * 60 * 60;
+        DisputeConfigParams {
+            voting_period: 7 * day,
+            reveal_period: 2 * day,
+            appeal_period: day,
+            min_stake: 1_000_000,
+            quorum: 5_000_000,
+            supermajority_bps: 6667,
+            max_appeals: 2,
+        }
+    }
+
+    #[test]
+    fn test_dispute_config_validation() {
+        let authority = Pubkey::new_unique();
+
+        let config = DisputeConfig::new(authority, dispute_config_params(), 254).unwrap();
+        assert_eq!(config.authority, authority);
+        assert_eq!(config.quorum, 5_000_000);
+        assert_eq!(config.supermajority_bps, 6667);
+        assert_eq!(config.max_appeals, 2);
+
+        let invalid = [
+            DisputeConfigParams { voting_period: 0, ..dispute_config_params() },
+            DisputeConfigParams { reveal_period: 0, ..dispute_config_params() },
+            DisputeConfigParams { appeal_period: 0, ..dispute_config_params() },
+            DisputeConfigParams { min_stake: 0, ..dispute_config_params() },
+            // Supermajority must be at least a simple majority and at most unanimity
+            DisputeConfigParams { supermajority_bps: 4999, ..dispute_config_params() },
+            DisputeConfigParams { supermajority_bps: 10001, ..dispute_config_params() },
+            DisputeConfigParams { max_appeals: MAX_DISPUTE_APPEALS + 1, ..dispute_config_params() },
+        ];
+        for params in invalid {
+            assert!(DisputeConfig::new(authority, params, 254).is_err());
+        }
+    }
+
+    #[test]
+    fn test_resolution_without_quorum_upholds_original() {
+        let mut dispute = create_empty_dispute();
+        dispute.quorum = 5_000_000;
+        record_revealed_votes(&mut dispute, &[(1, 4_000_000)]);
+
+        let resolution = dispute.calculate_resolution_at(1691234567).unwrap();
+        assert!(!resolution.quorum_reached);
+        assert_eq!(resolution.outcome, DisputeOutcome::UpholdOriginal);
+        assert_eq!(resolution.total_votes, 4_000_000);
+
+        // Reaching quorum makes the same vote binding
+        record_revealed_votes(&mut dispute, &[(1, 1_000_000)]);
+        let resolution = dispute.calculate_resolution_at(1691234567).unwrap();
+        assert!(resolution.quorum_reached);
+        assert_eq!(resolution.outcome, DisputeOutcome::OverrideOutcome(1));
+    }
+
+    #[test]
+    fn test_override_requires_supermajority() {
+        let mut dispute = create_empty_dispute();
+        dispute.supermajority_bps = 6667;
+        record_revealed_votes(&mut dispute, &[(0, 6_000_000), (UPHOLD_ORIGINAL_VOTE, 4_000_000)]);
+
+        // 60% is a majority but not a two-thirds supermajority
+        let resolution = dispute.calculate_resolution_at(1691234567).unwrap();
+        assert!(resolution.quorum_reached);
+        assert_eq!(resolution.outcome, DisputeOutcome::UpholdOriginal);
+        assert_eq!(resolution.winning_votes, 4_000_000);
+
+        record_revealed_votes(&mut dispute, &[(0, 3_000_000)]);
+        let resolution = dispute.calculate_resolution_at(1691234567).unwrap();
+        assert_eq!(resolution.outcome, DisputeOutcome::OverrideOutcome(0));
+        assert_eq!(resolution.winning_votes, 9_000_000);
+    }
+
+    #[test]
+    fn test_void_market_needs_supermajority_over_both_outcomes() {
+        let mut dispute = create_empty_dispute();
+        dispute.supermajority_bps = 6667;
+        assert!(Dispute::is_valid_vote_outcome(VOID_MARKET_VOTE));
+
+        // Voiding ties with outcome 0, so outcome 0 leads and lacks a supermajority
+        record_revealed_votes(&mut dispute, &[(VOID_MARKET_VOTE, 4_000_000), (0, 4_000_000)]);
+        let resolution = dispute.calculate_resolution_at(1691234567).unwrap();
+        assert_eq!(resolution.outcome, DisputeOutcome::UpholdOriginal);
+
+        record_revealed_votes(&mut dispute, &[(VOID_MARKET_VOTE, 12_000_000)]);
+        let resolution = dispute.calculate_resolution_at(1691234567).unwrap();
+        assert_eq!(resolution.outcome, DisputeOutcome::VoidMarket);
+        assert_eq!(resolution.winning_votes, 16_000_000);
+        assert!(resolution.is_winning_vote(VOID_MARKET_VOTE));
+        assert!(!resolution.is_winning_vote(0));
+    }
+
+    #[test]
+    fn test_void_ruling_rewards_disputer_and_void_voters() {
+        let (dispute, votes) = create_resolved_dispute(
+            vec![(VOID_MARKET_VOTE, 3_000_000), (UPHOLD_ORIGINAL_VOTE, 1_000_000)],
+            DisputeOutcome::VoidMarket,
+        );
+
+        assert!(dispute.disputer_reward().unwrap() > 0);
+        assert!(dispute.vote_reward(&votes[0]).unwrap() > 0);
+        assert_eq!(dispute.vote_reward(&votes[1]).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_no_quorum_returns_revealed_stake_in_full() {
+        let mut dispute = create_empty_dispute();
+        dispute.quorum = 10_000_000;
+        let winner = create_vote(&dispute, Some(UPHOLD_ORIGINAL_VOTE), 2_000_000);
+        let loser = create_vote(&dispute, Some(1), 1_000_000);
+        record_revealed_votes(&mut dispute, &[(UPHOLD_ORIGINAL_VOTE, 2_000_000), (1, 1_000_000)]);
+
+        let resolution = dispute.calculate_resolution_at(1691234567).unwrap();
+        dispute.resolve(resolution).unwrap();
+
+        assert_eq!(vote_payout(&dispute, &winner).unwrap(), 2_000_000);
+        assert_eq!(vote_payout(&dispute, &loser).unwrap(), 1_000_000);
+    }
+
+    // ============================================================================
+    // APPEAL ROUND UNIT TESTS
+    // ============================================================================
+
+    fn create_appealable_dispute(max_appeals: u8) -> Dispute {
+        let mut dispute = create_empty_dispute();
+        dispute.max_appeals = max_appeals;
+        dispute
+    }
+
+    /// Reveal the given votes in the current round and resolve it at the end of its reveal period
+    fn vote_and_resolve(dispute: &mut Dispute, votes: &[(u8, u64)]) -> Vec<DisputeVote> {
+        let round_votes = votes
+            .iter()
+            .map(|(outcome, weight)| DisputeVote {
+                round: dispute.round,
+                ..create_vote(dispute, Some(*outcome), *weight)
+            })
+            .collect();
+        record_revealed_votes(dispute, votes);
+        let resolution = dispute.calculate_resolution_at(dispute.reveal_end_time).unwrap();
+        dispute.resolve(resolution).unwrap();
+        round_votes
+    }
+
+    #[test]
+    fn test_three_round_appeal_escalation() {
+        let mut dispute = create_appealable_dispute(2);
+
+        // Round 0: voters overturn the report
+        let round_0_votes = vote_and_resolve(&mut dispute, &[(1, 3_000_000), (UPHOLD_ORIGINAL_VOTE, 1_000_000)]);
+        assert_eq!(dispute.resolution.as_ref().unwrap().outcome, DisputeOutcome::OverrideOutcome(1));
+        assert_eq!(dispute.appeal_deadline, dispute.reveal_end_time + dispute.appeal_period);
+
+        // First appeal doubles the original stake and opens a new voting period
+        let now = dispute.reveal_end_time + 60;
+        assert_eq!(dispute.appeal_at(now).unwrap(), 2_000_000);
+        assert_eq!(dispute.round, 1);
+        assert!(!dispute.is_resolved);
+        assert_eq!(dispute.voting_end_time, now + dispute.voting_period);
+        assert_eq!(dispute.reveal_end_time, dispute.voting_end_time + dispute.reveal_period);
+        assert_eq!(dispute.committed_votes, 0);
+
+        // Round 1: voters restore the report
+        let round_1_votes = vote_and_resolve(&mut dispute, &[(UPHOLD_ORIGINAL_VOTE, 8_000_000), (1, 2_000_000)]);
+        assert_eq!(dispute.resolution.as_ref().unwrap().outcome, DisputeOutcome::UpholdOriginal);
+
+        // Second appeal doubles again
+        let now = dispute.reveal_end_time + 60;
+        assert_eq!(dispute.appeal_at(now).unwrap(), 4_000_000);
+        assert_eq!(dispute.round, 2);
+
+        // Final round: its ruling is binding with no appeal window
+        vote_and_resolve(&mut dispute, &[(1, 20_000_000)]);
+        assert!(!dispute.can_appeal());
+        assert_eq!(dispute.appeal_deadline, dispute.reveal_end_time);
+        assert!(dispute.appeal_at(dispute.reveal_end_time).is_err());
+
+        dispute.finalize_at(dispute.reveal_end_time).unwrap();
+        assert!(dispute.is_finalized);
+        assert_eq!(dispute.resolution.as_ref().unwrap().outcome, DisputeOutcome::OverrideOutcome(1));
+        assert_eq!(dispute.past_rounds.len(), 2);
+        assert_eq!(dispute.past_rounds[1].stake_amount, 2_000_000);
+
+        // Earlier rounds pay out by their own rulings
+        let slashed = 1_000_000 * VOTER_SLASH_BPS as u64 / 10000;
+        assert_eq!(vote_payout(&dispute, &round_0_votes[0]).unwrap(), 3_000_000 + slashed);
+        assert_eq!(vote_payout(&dispute, &round_0_votes[1]).unwrap(), 1_000_000 - slashed);
+        let slashed = 2_000_000 * VOTER_SLASH_BPS as u64 / 10000;
+        assert_eq!(vote_payout(&dispute, &round_1_votes[0]).unwrap(), 8_000_000 + slashed);
+    }
+
+    #[test]
+    fn test_appeal_window() {
+        let mut dispute = create_appealable_dispute(1);
+
+        // Cannot appeal or finalize before the round is resolved
+        assert!(dispute.appeal_at(dispute.reveal_end_time).is_err());
+        assert!(dispute.finalize_at(dispute.reveal_end_time).is_err());
+
+        vote_and_resolve(&mut dispute, &[(0, 1_000_000)]);
+
+        // Ruling is not final while it can still be appealed
+        assert!(dispute.finalize_at(dispute.appeal_deadline - 1).is_err());
+        assert!(dispute.appeal_at(dispute.appeal_deadline).is_err());
+
+        dispute.finalize_at(dispute.appeal_deadline).unwrap();
+        assert!(dispute.finalize_at(dispute.appeal_deadline).is_err());
+        assert!(dispute.appeal_at(dispute.appeal_deadline - 1).is_err());
+    }
+
+    #[test]
+    fn test_dispute_without_appeals_is_final_after_first_round() {
+        let mut dispute = create_appealable_dispute(0);
+        vote_and_resolve(&mut dispute, &[(UPHOLD_ORIGINAL_VOTE, 1_000_000)]);
+
+        assert!(dispute.appeal_at(dispute.reveal_end_time).is_err());
+        assert!(dispute.finalize_at(dispute.reveal_end_time).is_ok());
+    }
+
+    // ============================================================================
+    // DISPUTED SETTLEMENT UNIT TESTS
+    // ============================================================================
+
+    fn create_settlement(total_payout: u64, winning_supply: u64) -> SettlementData {
+        SettlementData {
+            winning_outcome: 0,
+            settlement_timestamp: 1691234567,
+            oracle_data_hash: [1u8; 32],
+            total_payout,
+            winning_supply,
+            total_claimed: 0,
+        }
+    }
+
+    #[test]
+    fn test_payout_uses_winning_supply_snapshot() {
+        let mut settlement = create_settlement(10_000_000, 1_000);
+
+        let first = settlement.payout_for(250).unwrap();
+        settlement.total_claimed += first;
+        // Burned tokens do not change later claimers' share
+        let second = settlement.payout_for(750).unwrap();
+
+        assert_eq!(first, 2_500_000);
+        assert_eq!(second, 7_500_000);
+        assert_eq!(first + second, settlement.total_payout);
+
+        assert!(create_settlement(10_000_000, 0).payout_for(1).is_err());
+    }
+
+    #[test]
+    fn test_settlement_override_keeps_prior_claims_final() {
+        let mut settlement = create_settlement(10_000_000, 1_000);
+        settlement.total_claimed = 4_000_000;
+
+        settlement.override_outcome(1, [2u8; 32]).unwrap();
+
+        assert_eq!(settlement.winning_outcome, 1);
+        assert_eq!(settlement.oracle_data_hash, [2u8; 32]);
+        // Only what was left in the pool goes to the new winners
+        assert_eq!(settlement.total_payout, 6_000_000);
+        assert_eq!(settlement.unclaimed_payout().unwrap(), 6_000_000);
+        // Supply is snapshotted again from the new winning mint
+        assert_eq!(settlement.winning_supply, 0);
+    }
+
+    #[test]
+    fn test_oracle_data_override_keeps_integrity() {
+        let mut oracle_data = OracleData::from_report(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            0,
+            9500,
+            1691234567,
+            Vec::new(),
+        )
+        .unwrap();
+        oracle_data.dispute().unwrap();
+        let original_hash = oracle_data.data_hash;
+
+        oracle_data.override_outcome(1);
+
+        assert_eq!(oracle_data.winning_outcome, 1);
+        assert!(!oracle_data.is_disputed);
+        assert_ne!(oracle_data.data_hash, original_hash);
+        assert!(oracle_data.validate_data_integrity().unwrap());
+    }
+
+    // ============================================================================
+    // DISPUTE REWARD CLAIM UNIT TESTS
+    // ============================================================================
+
+    #[test]
+    fn test_rewards_are_separate_from_returned_stake() {
+        let (dispute, votes) = create_resolved_dispute(
+            vec![(UPHOLD_ORIGINAL_VOTE, 3_000_000), (1, 2_000_000)],
+            DisputeOutcome::UpholdOriginal,
+        );
+        let slashed = 2_000_000 * VOTER_SLASH_BPS as u64 / 10000;
+
+        assert_eq!(dispute.vote_principal(&votes[0]).unwrap(), 3_000_000);
+        assert_eq!(dispute.vote_reward(&votes[0]).unwrap(), slashed);
+        assert_eq!(dispute.vote_principal(&votes[1]).unwrap(), 2_000_000 - slashed);
+        assert_eq!(dispute.vote_reward(&votes[1]).unwrap(), 0);
+        // A disputer whose report was upheld has nothing to claim
+        assert_eq!(dispute.disputer_reward().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_forfeited_oracle_bond_shared_with_disputer() {
+        let (mut dispute, votes) = create_resolved_dispute(
+            vec![(0, 3_000_000), (UPHOLD_ORIGINAL_VOTE, 1_000_000)],
+            DisputeOutcome::OverrideOutcome(0),
+        );
+        dispute.oracle_bond = 4_000_000;
+        let slashed = 1_000_000 * VOTER_SLASH_BPS as u64 / 10000;
+
+        // Winning weight is the winning votes plus the disputer's 1_000_000 stake
+        let (pool, winning_weight) = dispute.reward_pool(0).unwrap();
+        assert_eq!(pool, slashed + 4_000_000);
+        assert_eq!(winning_weight, 4_000_000);
+
+        let voter_reward = dispute.vote_reward(&votes[0]).unwrap();
+        let disputer_reward = dispute.disputer_reward().unwrap();
+        assert_eq!(voter_reward, pool * 3 / 4);
+        assert_eq!(disputer_reward, pool / 4);
+        assert!(voter_reward + disputer_reward <= pool);
+    }
+
+    /// Everything paid out of a single-round dispute's escrow, which holds the
+    /// committed stake and any forfeited oracle bond
+    fn assert_payouts_drain_escrow(dispute: &Dispute, votes: &[DisputeVote]) {
+        let escrow = dispute.committed_votes + dispute.oracle_bond;
+        let paid: u64 = votes.iter().map(|vote| vote_payout(dispute, vote).unwrap()).sum::<u64>()
+            + dispute.disputer_reward().unwrap();
+
+        // Pro-rata shares round down, leaving at most one lamport per claimant
+        assert!(paid <= escrow);
+        assert!(escrow - paid <= votes.len() as u64 + 1);
+    }
+
+    #[test]
+    fn test_no_slashing_without_supermajority() {
+        let salt = [5u8; 32];
+        let mut dispute = create_empty_dispute();
+        dispute.supermajority_bps = 6667;
+        let mut votes = create_committed_votes(&mut dispute, &[
+            (Pubkey::new_unique(), 0, salt, 6_000_000),
+            (Pubkey::new_unique(), 1, salt, 4_000_000),
+        ]);
+        reveal(&mut dispute, &mut votes[0], 0, &salt);
+        reveal(&mut dispute, &mut votes[1], 1, &salt);
+
+        // Nobody voted to uphold, so the fallback ruling has no winners to pay
+        let resolution = dispute.calculate_resolution_at(1691234567).unwrap();
+        assert!(resolution.quorum_reached);
+        assert!(!resolution.decisive);
+        assert_eq!(resolution.outcome, DisputeOutcome::UpholdOriginal);
+        dispute.resolve(resolution).unwrap();
+
+        assert_eq!(dispute.reward_pool(0).unwrap(), (0, 0));
+        assert_eq!(vote_payout(&dispute, &votes[0]).unwrap(), 6_000_000);
+        assert_eq!(vote_payout(&dispute, &votes[1]).unwrap(), 4_000_000);
+        assert_payouts_drain_escrow(&dispute, &votes);
+    }
+
+    #[test]
+    fn test_decisive_payouts_drain_escrow() {
+        let salt = [6u8; 32];
+        for (outcomes, oracle_bond) in [
+            ([0, 0, 1, UPHOLD_ORIGINAL_VOTE], 4_000_000),
+            ([UPHOLD_ORIGINAL_VOTE, UPHOLD_ORIGINAL_VOTE, 1, VOID_MARKET_VOTE], 0),
+            ([VOID_MARKET_VOTE, VOID_MARKET_VOTE, VOID_MARKET_VOTE, 0], 2_500_001),
+        ] {
+            let mut dispute = create_empty_dispute();
+            let mut votes = create_committed_votes(&mut dispute, &[
+                (Pubkey::new_unique(), outcomes[0], salt, 3_333_333),
+                (Pubkey::new_unique(), outcomes[1], salt, 2_000_001),
+                (Pubkey::new_unique(), outcomes[2], salt, 1_000_003),
+                (Pubkey::new_unique(), outcomes[3], salt, 700_007),
+                // Never revealed, so forfeited to the winners
+                (Pubkey::new_unique(), outcomes[0], salt, 900_009),
+            ]);
+            for (vote, outcome) in votes.iter_mut().zip(outcomes) {
+                reveal(&mut dispute, vote, outcome, &salt);
+            }
+            let resolution = dispute.calculate_resolution_at(1691234567).unwrap();
+            assert!(resolution.decisive);
+            dispute.resolve(resolution).unwrap();
+            if dispute.resolution.as_ref().unwrap().outcome != DisputeOutcome::UpholdOriginal {
+                dispute.oracle_bond = oracle_bond;
+            }
+
+            assert_payouts_drain_escrow(&dispute, &votes);
+        }
+    }
+
+    #[test]
+    fn test_no_rewards_without_quorum() {
+        let mut dispute = create_empty_dispute();
+        dispute.quorum = 10_000_000;
+        let vote = create_vote(&dispute, Some(UPHOLD_ORIGINAL_VOTE), 2_000_000);
+        record_revealed_votes(&mut dispute, &[(UPHOLD_ORIGINAL_VOTE, 2_000_000), (1, 1_000_000)]);
+        let resolution = dispute.calculate_resolution_at(1691234567).unwrap();
+        dispute.resolve(resolution).unwrap();
+
+        assert_eq!(dispute.reward_pool(0).unwrap(), (0, 0));
+        assert_eq!(dispute.vote_reward(&vote).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_unrevealed_stake_refunded_without_quorum() {
+        let salt = [7u8; 32];
+        let mut dispute = create_empty_dispute();
+        dispute.quorum = 10_000_000;
+        let mut votes = create_committed_votes(&mut dispute, &[
+            (Pubkey::new_unique(), 1, salt, 3_000_000),
+            (Pubkey::new_unique(), UPHOLD_ORIGINAL_VOTE, salt, 2_000_000),
+            (Pubkey::new_unique(), 1, salt, 4_000_000),
+        ]);
+        reveal(&mut dispute, &mut votes[0], 1, &salt);
+        reveal(&mut dispute, &mut votes[1], UPHOLD_ORIGINAL_VOTE, &salt);
+        let resolution = dispute.calculate_resolution_at(1691234567).unwrap();
+        assert!(!resolution.quorum_reached);
+        dispute.resolve(resolution).unwrap();
+
+        // With no pool to forfeit into, the unrevealed stake goes back to its voter
+        assert_eq!(dispute.reward_pool(0).unwrap(), (0, 0));
+        assert_eq!(vote_payout(&dispute, &votes[2]).unwrap(), 4_000_000);
+        assert_payouts_drain_escrow(&dispute, &votes);
+    }
+
+    // ============================================================================
+    // DISPUTE EVIDENCE UNIT TESTS
+    // ============================================================================
+
+    #[test]
+    fn test_evidence_indices_are_sequential() {
+        let mut dispute = create_empty_dispute();
+        let now = dispute.submission_time + 60;
+
+        assert_eq!(dispute.record_evidence_at(now).unwrap(), 0);
+        assert_eq!(dispute.record_evidence_at(now).unwrap(), 1);
+        assert_eq!(dispute.evidence_count, 2);
+    }
+
+    #[test]
+    fn test_evidence_only_accepted_while_voting() {
+        let mut dispute = create_empty_dispute();
+
+        assert!(dispute.record_evidence_at(dispute.voting_end_time).is_err());
+
+        dispute.is_resolved = true;
+        assert!(dispute.record_evidence_at(dispute.submission_time).is_err());
+        assert_eq!(dispute.evidence_count, 0);
+    }
+
+    #[test]
+    fn test_evidence_limit() {
+        let mut dispute = create_empty_dispute();
+        dispute.evidence_count = MAX_DISPUTE_EVIDENCE;
+
+        assert!(dispute.record_evidence_at(dispute.submission_time).is_err());
+    }
+
+    #[test]
+    fn test_evidence_uri_validation() {
+        let new_evidence = |uri: String| {
+            DisputeEvidence::new(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                0,
+                0,
+                [7u8; 32],
+                uri,
+                1691234567,
+                255,
+            )
+        };
+
+        let evidence = new_evidence("ipfs://evidence".to_string()).unwrap();
+        assert_eq!(evidence.content_hash, [7u8; 32]);
+        assert!(new_evidence(String::new()).is_err());
+        assert!(new_evidence("a".repeat(MAX_EVIDENCE_URI_LEN + 1)).is_err());
+    }
+
+    // ============================================================================
+    // FEE DISTRIBUTION UNIT TESTS
+    // ============================================================================
+
+    fn create_fee_schedule() -> FeeSchedule {
+        FeeSchedule {
+            creator_bps: 3000,
+            protocol_bps: 2000,
+            lp_bps: 5000,
+        }
+    }
+
+    #[test]
+    fn test_fee_schedule_must_cover_whole_fee() {
+        assert!(create_fee_schedule().validate().is_ok());
+
+        let short = FeeSchedule { lp_bps: 4000, ..create_fee_schedule() };
+        assert!(short.validate().is_err());
+        let params = ProtocolConfigParams { fee_schedule: short, ..protocol_config_params() };
+        assert!(ProtocolConfig::new(Pubkey::new_unique(), params, 255).is_err());
+
+        let over = FeeSchedule { lp_bps: 6000, ..create_fee_schedule() };
+        assert!(over.validate().is_err());
+    }
+
+    #[test]
+    fn test_fee_split_rounding_goes_to_lps() {
+        let schedule = create_fee_schedule();
+
+        assert_eq!(schedule.split(10_000).unwrap(), (3_000, 2_000, 5_000));
+
+        // 3333 * 30% = 999.9 and 3333 * 20% = 666.6 round down; LPs take the dust
+        let (creator, protocol, lp) = schedule.split(3_333).unwrap();
+        assert_eq!((creator, protocol), (999, 666));
+        assert_eq!(creator + protocol + lp, 3_333);
+    }
+
+    #[test]
+    fn test_fee_accumulators_and_claims() {
+        let schedule = create_fee_schedule();
+        let mut fees = MarketFees::default();
+
+        // The creator and LP shares are handed back for their holders
+        assert_eq!(fees.accrue(10_000, &schedule).unwrap(), (3_000, 5_000));
+        assert_eq!(fees.accrue(5_000, &schedule).unwrap(), (1_500, 2_500));
+        assert_eq!(fees.creator_claimable(), 4_500);
+        assert_eq!(fees.protocol_claimable(), 3_000);
+        assert_eq!(fees.reserved(), 7_500);
+
+        fees.pay_creator(4_000).unwrap();
+        assert!(fees.pay_creator(501).is_err());
+        fees.pay_creator(500).unwrap();
+        assert_eq!(fees.reserved(), 3_000);
+
+        // Fees accrued after a claim become claimable again
+        fees.accrue(10_000, &schedule).unwrap();
+        assert_eq!(fees.creator_claimable(), 3_000);
+        fees.pay_creator(3_000).unwrap();
+        assert_eq!(fees.claim_protocol().unwrap(), 5_000);
+        assert_eq!(fees.reserved(), 0);
+    }
+
+    // ============================================================================
+    // PROTOCOL CONFIG UNIT TESTS
+    // ============================================================================
+
+    fn protocol_config_params() -> ProtocolConfigParams {
+        ProtocolConfigParams {
+            guardian: Pubkey::new_unique(),
+            treasury: Pubkey::new_unique(),
+            fee_schedule: create_fee_schedule(),
+            fee_bounds: FeeBounds {
+                min_fee_rate: 0,
+                max_fee_rate: 1000,
+            },
+            creation_fee: 10_000_000,
+            creator_bond: 100_000_000,
+            oracle_registry: Pubkey::new_unique(),
+        }
+    }
+
+    #[test]
+    fn test_protocol_config_fee_bounds() {
+        let config = ProtocolConfig::new(Pubkey::new_unique(), protocol_config_params(), 255).unwrap();
+        assert!(config.fee_bounds.validate_fee_rate(1000).is_ok());
+        assert!(config.fee_bounds.validate_fee_rate(1001).is_err());
+        assert!(BondingCurveParams::new(1_000_000, 1_000_000, 1_000_000, 1500, &config.fee_bounds).is_err());
+
+        // Raising the cap allows fee rates the old hardcoded 10% limit rejected
+        let raised = FeeBounds { min_fee_rate: 100, max_fee_rate: 2000 };
+        assert!(BondingCurveParams::new(1_000_000, 1_000_000, 1_000_000, 1500, &raised).is_ok());
+        assert!(BondingCurveParams::new(1_000_000, 1_000_000, 1_000_000, 50, &raised).is_err());
+
+        let inverted = ProtocolConfigParams {
+            fee_bounds: FeeBounds { min_fee_rate: 500, max_fee_rate: 100 },
+            ..protocol_config_params()
+        };
+        assert!(ProtocolConfig::new(Pubkey::new_unique(), inverted, 255).is_err());
+
+        let whole_trade = ProtocolConfigParams {
+            fee_bounds: FeeBounds { min_fee_rate: 0, max_fee_rate: 10000 },
+            ..protocol_config_params()
+        };
+        assert!(ProtocolConfig::new(Pubkey::new_unique(), whole_trade, 255).is_err());
+    }
+
+    #[test]
+    fn test_two_step_admin_transfer() {
+        let admin = Pubkey::new_unique();
+        let new_admin = Pubkey::new_unique();
+        let mut config = ProtocolConfig::new(admin, protocol_config_params(), 255).unwrap();
+
+        // Nothing pending yet
+        assert!(config.accept_admin(new_admin).is_err());
+
+        config.pending_admin = Some(new_admin);
+        assert!(config.accept_admin(Pubkey::new_unique()).is_err());
+        assert_eq!(config.admin, admin);
+
+        config.accept_admin(new_admin).unwrap();
+        assert_eq!(config.admin, new_admin);
+        assert_eq!(config.pending_admin, None);
+    }
+
+    // ============================================================================
+    // EMERGENCY PAUSE UNIT TESTS
+    // ============================================================================
+
+    /// Pause bit checked by each instruction's accounts
+    fn instruction_pause_flags() -> Vec<(&'static str, u8)> {
+        vec![
+            ("create_market", crate::CreateMarket::PAUSE_FLAG),
+            ("buy_tokens", crate::BuyTokens::PAUSE_FLAG),
+            ("sell_tokens", crate::SellTokens::PAUSE_FLAG),
+            ("set_resolution_condition", crate::SetResolutionCondition::PAUSE_FLAG),
+            ("set_optimistic_config", crate::SetOptimisticConfig::PAUSE_FLAG),
+            ("set_dynamic_fee", crate::SetDynamicFee::PAUSE_FLAG),
+            ("create_outcome_metadata", crate::CreateOutcomeMetadata::PAUSE_FLAG),
+            ("settle_market", crate::SettleMarket::PAUSE_FLAG),
+            ("settle_market_switchboard", crate::SettleMarketSwitchboard::PAUSE_FLAG),
+            ("submit_signed_report", crate::SubmitSignedReport::PAUSE_FLAG),
+            ("propose_outcome", crate::ProposeOutcome::PAUSE_FLAG),
+            ("finalize_proposal", crate::FinalizeProposal::PAUSE_FLAG),
+            ("claim_payout", crate::ClaimPayout::PAUSE_FLAG),
+            ("release_creator_bond", crate::ReleaseCreatorBond::PAUSE_FLAG),
+            ("claim_creator_fees", crate::ClaimCreatorFees::PAUSE_FLAG),
+            ("claim_unshared_creator_fees", crate::ClaimUnsharedCreatorFees::PAUSE_FLAG),
+            ("claim_protocol_fees", crate::ClaimProtocolFees::PAUSE_FLAG),
+            ("add_liquidity", crate::AddLiquidity::PAUSE_FLAG),
+            ("remove_liquidity", crate::RemoveLiquidity::PAUSE_FLAG),
+            ("initialize_creator_shares", crate::InitializeCreatorShares::PAUSE_FLAG),
+            ("stake_creator_shares", crate::StakeCreatorShares::PAUSE_FLAG),
+            ("unstake_creator_shares", crate::UnstakeCreatorShares::PAUSE_FLAG),
+            ("withdraw_vote_stake", crate::WithdrawVoteStake::PAUSE_FLAG),
+            ("claim_dispute_reward", crate::ClaimDisputeReward::PAUSE_FLAG),
+            ("settle_dispute_stake", crate::SettleDisputeStake::PAUSE_FLAG),
+            ("settle_appeal_stake", crate::SettleAppealStake::PAUSE_FLAG),
+            ("submit_dispute", crate::SubmitDispute::PAUSE_FLAG),
+            ("dispute_proposal", crate::DisputeProposal::PAUSE_FLAG),
+            ("add_evidence", crate::AddEvidence::PAUSE_FLAG),
+            ("commit_vote", crate::CommitVote::PAUSE_FLAG),
+            ("reveal_vote", crate::RevealVote::PAUSE_FLAG),
+            ("resolve_dispute", crate::ResolveDispute::PAUSE_FLAG),
+            ("appeal_dispute", crate::AppealDispute::PAUSE_FLAG),
+            ("finalize_dispute", crate::FinalizeDispute::PAUSE_FLAG),
+        ]
+    }
+
+    #[test]
+    fn test_each_instruction_respects_its_pause_bit() {
+        let expected = [
+            (PAUSE_TRADING, vec![
+                "create_market", "buy_tokens", "sell_tokens", "add_liquidity",
+                "initialize_creator_shares", "stake_creator_shares", "set_dynamic_fee",
+                "create_outcome_metadata",
+            ]),
+            (PAUSE_SETTLEMENT, vec![
+                "set_resolution_condition", "set_optimistic_config", "settle_market",
+                "settle_market_switchboard", "submit_signed_report", "propose_outcome",
+                "finalize_proposal",
+            ]),
+            (PAUSE_CLAIMS, vec![
+                "claim_payout", "release_creator_bond", "claim_creator_fees", "claim_unshared_creator_fees",
+                "claim_protocol_fees", "remove_liquidity",
+                "unstake_creator_shares", "withdraw_vote_stake",
+                "claim_dispute_reward", "settle_dispute_stake", "settle_appeal_stake",
+            ]),
+            (PAUSE_DISPUTES, vec![
+                "submit_dispute", "dispute_proposal", "add_evidence", "commit_vote", "reveal_vote",
+                "resolve_dispute", "appeal_dispute", "finalize_dispute",
+            ]),
+        ];
+        let mut config = ProtocolConfig::new(Pubkey::new_unique(), protocol_config_params(), 255).unwrap();
+
+        for (flag, paused_instructions) in expected.iter() {
+            config.paused = *flag;
+            for (name, instruction_flag) in instruction_pause_flags() {
+                assert_eq!(
+                    config.is_paused(instruction_flag, 0),
+                    paused_instructions.contains(&name),
+                    "{} with pause bits {:#06b}",
+                    name,
+                    flag
+                );
+            }
+        }
+
+        config.paused = 0;
+        assert!(instruction_pause_flags().iter().all(|(_, flag)| !config.is_paused(*flag, 0)));
+        config.paused = PAUSE_ALL;
+        assert!(instruction_pause_flags().iter().all(|(_, flag)| config.is_paused(*flag, 0)));
+    }
+
+    /// Sources of the modules declaring instruction accounts
+    const ACCOUNTS_SOURCES: [&str; 9] = [
+        include_str!("lib.rs"),
+        include_str!("trading.rs"),
+        include_str!("liquidity.rs"),
+        include_str!("creator_shares.rs"),
+        include_str!("settlement.rs"),
+        include_str!("switchboard.rs"),
+        include_str!("signed_report.rs"),
+        include_str!("optimistic.rs"),
+        include_str!("metaplex.rs"),
+    ];
+
+    #[test]
+    fn test_each_instruction_checks_its_pause_bit() {
+        let table = include_str!("pause.rs");
+        let table = &table[table.find("pause_flags! {").unwrap()..];
+        let table = &table[..table.find("\n}").unwrap()];
+        let accounts: Vec<&str> = table
+            .lines()
+            .skip(1)
+            .filter_map(|line| line.trim().split(" =>").next())
+            .collect();
+        assert_eq!(accounts.len(), instruction_pause_flags().len());
+
+        for name in accounts {
+            let declaration = format!("pub struct {}<'info> {{", name);
+            let body = ACCOUNTS_SOURCES
+                .iter()
+                .find_map(|source| {
+                    let body = &source[source.find(&declaration)?..];
+                    Some(&body[..body.find("\n}\n").unwrap()])
+                })
+                .unwrap_or_else(|| panic!("{} accounts not found", name));
+
+            // Only market creation runs before there is a market to pause
+            let market_paused = if name == "CreateMarket" { "0" } else { "market.paused" };
+            assert!(
+                body.contains(&format!("!protocol_config.is_paused({}::PAUSE_FLAG, {})", name, market_paused)),
+                "{} does not check its pause bit against the market",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_market_pause_overrides_protocol() {
+        let config = ProtocolConfig::new(Pubkey::new_unique(), protocol_config_params(), 255).unwrap();
+
+        // A market can freeze claims while trading elsewhere continues
+        assert!(config.is_paused(PAUSE_CLAIMS, PAUSE_CLAIMS));
+        assert!(!config.is_paused(PAUSE_TRADING, PAUSE_CLAIMS));
+        assert!(!config.is_paused(PAUSE_CLAIMS, 0));
+    }
+
+    #[test]
+    fn test_only_guardian_or_admin_can_pause() {
+        let admin = Pubkey::new_unique();
+        let params = protocol_config_params();
+        let guardian = params.guardian;
+        let config = ProtocolConfig::new(admin, params, 255).unwrap();
+
+        assert!(config.can_pause(&guardian));
+        assert!(config.can_pause(&admin));
+        assert!(!config.can_pause(&Pubkey::new_unique()));
+    }
+
+    // ============================================================================
+    // LIQUIDITY POOL UNIT TESTS
+    // ============================================================================
+
+    fn create_lp_position() -> LpPosition {
+        LpPosition {
+            market: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            shares: 0,
+            reward_debt: 0,
+            fees_owed: 0,
+            unlocks_at: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_lp_fees_follow_share_of_pool() {
+        let mut pool = LiquidityPool::default();
+        let mut alice = create_lp_position();
+        let mut bob = create_lp_position();
+
+        // No LPs yet: the fee is not credited to the pool
+        assert!(!pool.distribute_fee(1_000).unwrap());
+
+        let shares = pool.shares_for_deposit(3_000_000).unwrap();
+        alice.deposit(shares, pool.fee_per_share).unwrap();
+        pool.deposit(3_000_000, shares).unwrap();
+        assert!(pool.distribute_fee(3_000).unwrap());
+
+        // Bob joins later and only earns fees from then on
+        let shares = pool.shares_for_deposit(1_000_000).unwrap();
+        bob.deposit(shares, pool.fee_per_share).unwrap();
+        pool.deposit(1_000_000, shares).unwrap();
+        assert!(pool.distribute_fee(4_000).unwrap());
+
+        assert_eq!(alice.withdraw(alice.shares, pool.fee_per_share).unwrap(), 3_000 + 3_000);
+        assert_eq!(bob.withdraw(bob.shares / 2, pool.fee_per_share).unwrap(), 1_000);
+
+        // Fees are paid once; the remaining shares start from a fresh checkpoint
+        assert_eq!(bob.withdraw(bob.shares, pool.fee_per_share).unwrap(), 0);
+        assert_eq!(bob.shares, 0);
+    }
+
+    #[test]
+    fn test_lp_withdrawal_is_proportional() {
+        let mut pool = LiquidityPool::default();
+        pool.deposit(3_000_000, 3_000_000).unwrap();
+        pool.deposit(1_000_000, 1_000_000).unwrap();
+
+        assert_eq!(pool.collateral_for_shares(1_000_000).unwrap(), 1_000_000);
+        assert!(pool.collateral_for_shares(5_000_000).is_err());
+
+        pool.withdraw(1_000_000, 1_000_000).unwrap();
+        assert_eq!(pool.collateral, 3_000_000);
+        assert_eq!(pool.total_shares, 3_000_000);
+        assert_eq!(pool.shares_for_deposit(1_500_000).unwrap(), 1_500_000);
+    }
+
+    #[test]
+    fn test_lp_position_locked_after_deposit() {
+        let mut position = create_lp_position();
+        let now = 1691234567;
+
+        position.lock_at(now, now + 10 * LP_LOCKUP);
+        assert!(position.require_unlocked_at(now + LP_LOCKUP - 1).is_err());
+        assert!(position.require_unlocked_at(now + LP_LOCKUP).is_ok());
+
+        // The lockup never outlasts trading
+        position.lock_at(now, now + 60);
+        assert!(position.require_unlocked_at(now + 60).is_ok());
+    }
+
+    #[test]
+    fn test_lp_position_cannot_overdraw() {
+        let mut position = create_lp_position();
+        position.deposit(1_000, 0).unwrap();
+
+        assert!(position.withdraw(1_001, 0).is_err());
+        assert_eq!(position.shares, 1_000);
+    }
+
+    // ============================================================================
+    // CREATOR SHARES UNIT TESTS
+    // ============================================================================
+
+    fn create_creator_stake() -> CreatorStake {
+        CreatorStake {
+            market: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            staked: 0,
+            reward_debt: 0,
+            rewards_owed: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_creator_fees_split_pro_rata_between_stakers() {
+        let mut rewards = CreatorRewards::default();
+        let mut alice = create_creator_stake();
+        let mut bob = create_creator_stake();
+
+        alice.deposit(300_000, rewards.reward_per_token).unwrap();
+        rewards.stake(300_000).unwrap();
+        bob.deposit(100_000, rewards.reward_per_token).unwrap();
+        rewards.stake(100_000).unwrap();
+        rewards.distribute(4_000).unwrap();
+
+        assert_eq!(alice.claim(rewards.reward_per_token).unwrap(), 3_000);
+        assert_eq!(bob.claim(rewards.reward_per_token).unwrap(), 1_000);
+        assert!(alice.claim(rewards.reward_per_token).is_err());
+    }
+
+    #[test]
+    fn test_late_staker_earns_only_after_joining() {
+        let mut rewards = CreatorRewards::default();
+        let mut alice = create_creator_stake();
+        let mut bob = create_creator_stake();
+
+        alice.deposit(100_000, rewards.reward_per_token).unwrap();
+        rewards.stake(100_000).unwrap();
+        rewards.distribute(2_000).unwrap();
+
+        bob.deposit(100_000, rewards.reward_per_token).unwrap();
+        rewards.stake(100_000).unwrap();
+        rewards.distribute(2_000).unwrap();
+
+        // Unstaking keeps what was earned but stops further accrual
+        alice.withdraw(100_000, rewards.reward_per_token).unwrap();
+        rewards.unstake(100_000).unwrap();
+        rewards.distribute(1_000).unwrap();
+
+        assert_eq!(alice.claim(rewards.reward_per_token).unwrap(), 3_000);
+        assert_eq!(bob.claim(rewards.reward_per_token).unwrap(), 2_000);
+    }
+
+    #[test]
+    fn test_unstaked_creator_fees_stay_with_creator() {
+        let mut rewards = CreatorRewards::default();
+        let mut stake = create_creator_stake();
+
+        rewards.distribute(5_000).unwrap();
+        assert_eq!(rewards.undistributed, 5_000);
+        assert_eq!(rewards.reward_per_token, 0);
+
+        // A late staker, however small, earns nothing from the backlog
+        stake.deposit(1, rewards.reward_per_token).unwrap();
+        rewards.stake(1).unwrap();
+        assert!(stake.claim(rewards.reward_per_token).is_err());
+
+        rewards.distribute(1_000).unwrap();
+        assert_eq!(stake.claim(rewards.reward_per_token).unwrap(), 1_000);
+        assert_eq!(rewards.take_undistributed().unwrap(), 5_000);
+    }
+
+    #[test]
+    fn test_unshared_creator_fees_taken_once() {
+        let mut rewards = CreatorRewards::default();
+        rewards.distribute(2_500).unwrap();
+
+        assert_eq!(rewards.take_undistributed().unwrap(), 2_500);
+        assert_eq!(rewards.undistributed, 0);
+        assert!(rewards.take_undistributed().is_err());
+    }
+
+    #[test]
+    fn test_creator_stake_cannot_overdraw() {
+        let mut rewards = CreatorRewards::default();
+        let mut stake = create_creator_stake();
+        stake.deposit(1_000, 0).unwrap();
+        rewards.stake(1_000).unwrap();
+
+        assert!(stake.withdraw(1_001, 0).is_err());
+        assert!(rewards.unstake(1_001).is_err());
+        assert_eq!(stake.staked, 1_000);
+        assert_eq!(rewards.total_staked, 1_000);
+    }
+
+    // ============================================================================
+    // DYNAMIC FEE UNIT TESTS
+    // ============================================================================
+
+    fn create_dynamic_fee() -> DynamicFee {
+        let params = DynamicFeeParams {
+            min_fee_rate: 100,
+            max_fee_rate: 500,
+            target_volume: 1_000_000,
+            half_life: 3600,
+        };
+        DynamicFee::new(params, &protocol_config_params().fee_bounds).unwrap()
+    }
+
+    #[test]
+    fn test_dynamic_fee_rises_with_volume() {
+        let mut dynamic_fee = create_dynamic_fee();
+        assert_eq!(dynamic_fee.fee_rate_at(1_000), 100);
+
+        dynamic_fee.record_volume_at(250_000, 1_000).unwrap();
+        assert_eq!(dynamic_fee.fee_rate_at(1_000), 200);
+
+        dynamic_fee.record_volume_at(250_000, 1_000).unwrap();
+        assert_eq!(dynamic_fee.fee_rate_at(1_000), 300);
+
+        // Capped at the maximum once recent volume passes the target
+        dynamic_fee.record_volume_at(5_000_000, 1_000).unwrap();
+        assert_eq!(dynamic_fee.fee_rate_at(1_000), 500);
+    }
+
+    #[test]
+    fn test_dynamic_fee_volume_decays() {
+        let mut dynamic_fee = create_dynamic_fee();
+        dynamic_fee.record_volume_at(800_000, 0).unwrap();
+
+        assert_eq!(dynamic_fee.volume_ema_at(3600), 400_000);
+        assert_eq!(dynamic_fee.volume_ema_at(7200), 200_000);
+        // Halfway through a half-life decays by a quarter
+        assert_eq!(dynamic_fee.volume_ema_at(1800), 600_000);
+        assert_eq!(dynamic_fee.volume_ema_at(3600 * 100), 0);
+
+        // New volume lands on top of the decayed value
+        dynamic_fee.record_volume_at(100_000, 3600).unwrap();
+        assert_eq!(dynamic_fee.volume_ema, 500_000);
+        assert_eq!(dynamic_fee.fee_rate_at(3600), 300);
+    }
+
+    #[test]
+    fn test_dynamic_fee_validation() {
+        let params = DynamicFeeParams {
+            min_fee_rate: 100,
+            max_fee_rate: 500,
+            target_volume: 1_000_000,
+            half_life: 3600,
+        };
+        let bounds = protocol_config_params().fee_bounds;
+        assert!(DynamicFee::new(params.clone(), &bounds).is_ok());
+
+        assert!(DynamicFee::new(DynamicFeeParams { min_fee_rate: 600, ..params.clone() }, &bounds).is_err());
+        assert!(DynamicFee::new(DynamicFeeParams { max_fee_rate: 5_000, ..params.clone() }, &bounds).is_err());
+        assert!(DynamicFee::new(DynamicFeeParams { target_volume: 0, ..params.clone() }, &bounds).is_err());
+        assert!(DynamicFee::new(DynamicFeeParams { half_life: 0, ..params }, &bounds).is_err());
+    }
+
+    // ============================================================================
+    // CREATOR BOND UNIT TESTS
+    // ============================================================================
+
+    fn settled_status(settlement_timestamp: i64) -> MarketStatus {
+        MarketStatus {
+            is_active: true,
+            is_settled: true,
+            is_disputed: false,
+            is_voided: false,
+            winning_outcome: Some(0),
+            settlement_timestamp: Some(settlement_timestamp),
+        }
+    }
+
+    #[test]
+    fn test_creator_bond_refunded_after_lockup() {
+        let settled_at = 1691234567;
+        let status = settled_status(settled_at);
+
+        assert!(status.creator_bond_refund_at(settled_at + CREATOR_BOND_LOCKUP - 1).is_err());
+        assert!(status.creator_bond_refund_at(settled_at + CREATOR_BOND_LOCKUP).unwrap());
+    }
+
+    #[test]
+    fn test_creator_bond_held_while_unsettled_or_disputed() {
+        let mut status = settled_status(1691234567);
+        let later = 1691234567 + CREATOR_BOND_LOCKUP;
+
+        status.is_disputed = true;
+        assert!(status.creator_bond_refund_at(later).is_err());
+
+        status.is_disputed = false;
+        status.is_settled = false;
+        assert!(status.creator_bond_refund_at(later).is_err());
+    }
+
+    #[test]
+    fn test_creator_bond_slashed_when_voided() {
+        let mut status = settled_status(1691234567);
+        status.is_voided = true;
+        status.winning_outcome = None;
+
+        // No lockup: a voided market's bond can go to the treasury at once
+        assert!(!status.creator_bond_refund_at(1691234567).unwrap());
+    }
+
+    // ============================================================================
+    // OUTCOME MINT UNIT TESTS
+    // ============================================================================
+
+    #[test]
+    fn test_outcome_mint_names() {
+        let description = "Will BTC reach $100k by end of 2024?";
+
+        assert_eq!(outcome_mint_name(description, 0), "Will BTC reach $100k by end of 2024? – YES");
+        assert_eq!(outcome_mint_name(description, 1), "Will BTC reach $100k by end of 2024? – NO");
+    }
+
+    // ============================================================================
+    // MARKET CONFIGURATION UNIT TESTS
+    // ============================================================================
+
+    fn create_test_market() -> Market {
+        let config = ProtocolConfig::new(Pubkey::new_unique(), protocol_config_params(), 255).unwrap();
+        Market {
+            creator: Pubkey::new_unique(),
+            description: "Will BTC reach $100k by end of 2024?".to_string(),
+            resolution_date: 1691234567,
+            oracle_source: Pubkey::new_unique(),
+            outcome_tokens: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+            collateral_mint: None,
+            collateral_decimals: NATIVE_SOL_DECIMALS,
+            bonding_curve_params: create_test_bonding_curve_params(),
+            dynamic_fee: None,
+            fee_schedule: config.fee_schedule.clone(),
+            fees: MarketFees::default(),
+            liquidity: LiquidityPool::default(),
+            creator_rewards: CreatorRewards::default(),
+            total_volume: 0,
+            creator_bond: config.creator_bond,
+            status: MarketStatus {
+                is_active: true,
+                is_settled: false,
+                is_disputed: false,
+                is_voided: false,
+                winning_outcome: None,
+                settlement_timestamp: None,
+            },
+            settlement_data: None,
+            resolution_condition: None,
+            optimistic_config: None,
+            paused: 0,
+        }
+    }
+
+    #[test]
+    fn test_market_settings_lock_after_first_trade() {
+        let mut market = create_test_market();
+        assert!(market.require_untraded().is_ok());
+
+        market.record_trade(1_000_000, 10_000, 1691234000).unwrap();
+        assert!(market.require_untraded().is_err());
+    }
+
+    #[test]
+    fn test_settled_market_settings_are_locked() {
+        let mut market = create_test_market();
+        market.status.is_settled = true;
+
+        assert!(market.require_untraded().is_err());
+    }
+
+    // ============================================================================
+    // COLLATERAL UNIT TESTS
+    // ============================================================================
+
+    thread_local! {
+        static CPI_LOG: RefCell<Vec<Instruction>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Serves the rent sysvar and records CPIs instead of running them
+    struct CollateralStubs;
+
+    impl SyscallStubs for CollateralStubs {
+        fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { *(var_addr as *mut Rent) = Rent::default() };
+            SUCCESS
+        }
+
+        fn sol_invoke_signed(
+            &self,
+            instruction: &Instruction,
+            _account_infos: &[AccountInfo],
+            _signers_seeds: &[&[&[u8]]],
+        ) -> ProgramResult {
+            CPI_LOG.with(|log| log.borrow_mut().push(instruction.clone()));
+            Ok(())
+        }
+    }
+
+    fn take_cpis() -> Vec<Instruction> {
+        static STUBS: Once = Once::new();
+        STUBS.call_once(|| {
+            set_syscall_stubs(Box::new(CollateralStubs));
+        });
+        CPI_LOG.with(|log| log.take())
+    }
+
+    fn test_account(key: Pubkey, owner: Pubkey, lamports: u64, data: Vec<u8>) -> AccountInfo<'static> {
+        AccountInfo::new(
+            Box::leak(Box::new(key)),
+            false,
+            true,
+            Box::leak(Box::new(lamports)),
+            Box::leak(data.into_boxed_slice()),
+            Box::leak(Box::new(owner)),
+            false,
+            0,
+        )
+    }
+
+    fn test_token_account(key: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64) -> Account<'static, TokenAccount> {
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            state: AccountState::Initialized,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        let info = test_account(key, spl_token::ID, 1, data);
+        Account::try_from(Box::leak(Box::new(info))).unwrap()
+    }
+
+    struct CollateralFixture {
+        market: Market,
+        market_vault: AccountInfo<'static>,
+        trader: AccountInfo<'static>,
+        collateral: Collateral<'static>,
+    }
+
+    /// SPL market with 6-decimal collateral whose vault ATA holds 5_000_000
+    fn create_spl_collateral_fixture() -> CollateralFixture {
+        let market_vault = test_account(Pubkey::new_unique(), crate::ID, 1, vec![0; 8 + 32 + 1]);
+        let trader = test_account(Pubkey::new_unique(), system_program::ID, 1, vec![]);
+        let mint_key = Pubkey::new_unique();
+
+        let mut mint_data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint { decimals: 6, is_initialized: true, ..Default::default() }
+            .pack_into_slice(&mut mint_data);
+        let mint = test_account(mint_key, spl_token::ID, 1, mint_data);
+
+        let mut token_program = test_account(spl_token::ID, Pubkey::default(), 1, vec![]);
+        token_program.executable = true;
+
+        let vault_ata = get_associated_token_address(market_vault.key, &mint_key);
+        let mut market = create_test_market();
+        market.collateral_mint = Some(mint_key);
+        market.collateral_decimals = 6;
+
+        CollateralFixture {
+            market,
+            collateral: Collateral {
+                collateral_mint: Some(Account::try_from(Box::leak(Box::new(mint))).unwrap()),
+                vault_token_account: Some(test_token_account(vault_ata, mint_key, *market_vault.key, 5_000_000)),
+                party_token_account: Some(test_token_account(Pubkey::new_unique(), mint_key, *trader.key, 1_000_000)),
+                token_program: Some(Program::try_from(&*Box::leak(Box::new(token_program))).unwrap()),
+            },
+            market_vault,
+            trader,
+        }
+    }
+
+    fn market_account(market: &Market) -> Account<'static, Market> {
+        let mut data = Vec::new();
+        market.try_serialize(&mut data).unwrap();
+        let info = test_account(Pubkey::new_unique(), crate::ID, 1, data);
+        Account::try_from(Box::leak(Box::new(info))).unwrap()
+    }
+
+    /// Asserts `cpi` is a token transfer_checked of `amount` between the given accounts
+    fn assert_transfer_checked(cpi: &Instruction, from: &Pubkey, to: &Pubkey, authority: &Pubkey, amount: u64) {
+        assert_eq!(cpi.program_id, spl_token::ID);
+        assert_eq!(cpi.accounts[0].pubkey, *from);
+        assert_eq!(cpi.accounts[2].pubkey, *to);
+        assert_eq!(cpi.accounts[3].pubkey, *authority);
+        assert!(cpi.accounts[3].is_signer);
+        assert_eq!(
+            TokenInstruction::unpack(&cpi.data).unwrap(),
+            TokenInstruction::TransferChecked { amount, decimals: 6 }
+        );
+    }
+
+    #[test]
+    fn test_sol_collateral_balance_excludes_rent_and_creator_bond() {
+        take_cpis();
+        let market = create_test_market();
+        let rent_exempt_minimum = Rent::default().minimum_balance(8 + 32 + 1);
+        let market_vault = test_account(
+            Pubkey::new_unique(),
+            crate::ID,
+            rent_exempt_minimum + market.creator_bond + 700_000,
+            vec![0; 8 + 32 + 1],
+        );
+        let collateral = Collateral {
+            collateral_mint: None,
+            vault_token_account: None,
+            party_token_account: None,
+            token_program: None,
+        };
+
+        assert_eq!(collateral.balance(&market, &market_vault).unwrap(), 700_000);
+
+        // A vault still short of its bond has nothing backing outcome tokens
+        **market_vault.try_borrow_mut_lamports().unwrap() = rent_exempt_minimum + market.creator_bond / 2;
+        assert_eq!(collateral.balance(&market, &market_vault).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_sol_collateral_withdraw_moves_vault_lamports() {
+        let market = create_test_market();
+        let market_vault = test_account(Pubkey::new_unique(), crate::ID, 1_000_000, vec![0; 8 + 32 + 1]);
+        let recipient = test_account(Pubkey::new_unique(), system_program::ID, 0, vec![]);
+        let collateral = Collateral {
+            collateral_mint: None,
+            vault_token_account: None,
+            party_token_account: None,
+            token_program: None,
+        };
+        let market = market_account(&market);
+
+        collateral.withdraw(&market, &market_vault, 255, &recipient, 400_000).unwrap();
+        assert_eq!(market_vault.lamports(), 600_000);
+        assert_eq!(recipient.lamports(), 400_000);
+
+        assert!(collateral.withdraw(&market, &market_vault, 255, &recipient, 600_001).is_err());
+    }
+
+    #[test]
+    fn test_spl_collateral_balance_reads_vault_token_account() {
+        let mut fixture = create_spl_collateral_fixture();
+        // Vault lamports and the SOL creator bond play no part in SPL markets
+        assert_eq!(fixture.collateral.balance(&fixture.market, &fixture.market_vault).unwrap(), 5_000_000);
+
+        fixture.market.liquidity.collateral = 2_000_000;
+        assert_eq!(fixture.collateral.balance(&fixture.market, &fixture.market_vault).unwrap(), 3_000_000);
+    }
+
+    #[test]
+    fn test_spl_collateral_deposit_transfers_from_trader() {
+        take_cpis();
+        let fixture = create_spl_collateral_fixture();
+        let system = test_account(system_program::ID, Pubkey::default(), 1, vec![]);
+
+        fixture
+            .collateral
+            .deposit(&fixture.market, &fixture.market_vault, &fixture.trader, &system, 250_000)
+            .unwrap();
+
+        let cpis = take_cpis();
+        assert_eq!(cpis.len(), 1);
+        assert_transfer_checked(
+            &cpis[0],
+            &fixture.collateral.party_token_account.as_ref().unwrap().key(),
+            &fixture.collateral.vault_token_account.as_ref().unwrap().key(),
+            fixture.trader.key,
+            250_000,
+        );
+    }
+
+    #[test]
+    fn test_curve_prices_charged_in_collateral_base_units() {
+        take_cpis();
+        let mut fixture = create_spl_collateral_fixture();
+        let system = test_account(system_program::ID, Pubkey::default(), 1, vec![]);
+
+        // 0.01 of a 6-decimal USDC-like collateral, with no conversion from SOL
+        fixture.market.bonding_curve_params.initial_price = 10_000;
+        let (cost, fee) = BondingCurve::calculate_buy_quote(&fixture.market.bonding_curve_params, 0, 100).unwrap();
+        assert_eq!((cost, fee), (1_011_010, 10_010));
+
+        fixture
+            .collateral
+            .deposit(&fixture.market, &fixture.market_vault, &fixture.trader, &system, cost)
+            .unwrap();
+        assert_transfer_checked(
+            &take_cpis()[0],
+            &fixture.collateral.party_token_account.as_ref().unwrap().key(),
+            &fixture.collateral.vault_token_account.as_ref().unwrap().key(),
+            fixture.trader.key,
+            1_011_010,
+        );
+    }
+
+    #[test]
+    fn test_spl_collateral_withdraw_signs_as_vault() {
+        take_cpis();
+        let fixture = create_spl_collateral_fixture();
+        let market = market_account(&fixture.market);
+
+        fixture
+            .collateral
+            .withdraw(&market, &fixture.market_vault, 255, &fixture.trader, 125_000)
+            .unwrap();
+
+        let cpis = take_cpis();
+        assert_eq!(cpis.len(), 1);
+        assert_transfer_checked(
+            &cpis[0],
+            &fixture.collateral.vault_token_account.as_ref().unwrap().key(),
+            &fixture.collateral.party_token_account.as_ref().unwrap().key(),
+            fixture.market_vault.key,
+            125_000,
+        );
+    }
+
+    #[test]
+    fn test_spl_collateral_rejects_mismatched_accounts() {
+        take_cpis();
+        let system = test_account(system_program::ID, Pubkey::default(), 1, vec![]);
+
+        // Collateral mint other than the market's
+        let mut fixture = create_spl_collateral_fixture();
+        fixture.market.collateral_mint = Some(Pubkey::new_unique());
+        assert!(fixture.collateral.balance(&fixture.market, &fixture.market_vault).is_err());
+
+        // Vault token account that is not the vault PDA's associated token account
+        let mut fixture = create_spl_collateral_fixture();
+        let mint = fixture.market.collateral_mint.unwrap();
+        fixture.collateral.vault_token_account =
+            Some(test_token_account(Pubkey::new_unique(), mint, *fixture.market_vault.key, 5_000_000));
+        assert!(fixture.collateral.balance(&fixture.market, &fixture.market_vault).is_err());
+
+        // SPL market called without its collateral accounts
+        let mut fixture = create_spl_collateral_fixture();
+        fixture.collateral.collateral_mint = None;
+        assert!(fixture.collateral.balance(&fixture.market, &fixture.market_vault).is_err());
+
+        let mut fixture = create_spl_collateral_fixture();
+        fixture.collateral.token_program = None;
+        assert!(fixture
+            .collateral
+            .deposit(&fixture.market, &fixture.market_vault, &fixture.trader, &system, 1)
+            .is_err());
+
+        // Trader token account owned by someone else
+        let fixture = create_spl_collateral_fixture();
+        let other = test_account(Pubkey::new_unique(), system_program::ID, 1, vec![]);
+        assert!(fixture
+            .collateral
+            .deposit(&fixture.market, &fixture.market_vault, &other, &system, 1)
+            .is_err());
+        let market = market_account(&fixture.market);
+        assert!(fixture.collateral.withdraw(&market, &fixture.market_vault, 255, &other, 1).is_err());
+
+        assert!(take_cpis().is_empty());
+    }
+}
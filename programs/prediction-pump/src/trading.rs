@@ -0,0 +1,283 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{burn, mint_to, Burn, Mint, MintTo, TokenAccount, TokenInterface};
+
+use crate::{BondingCurve, Market, MarketVault, PauseFlag, PredictionPumpError, ProtocolConfig};
+use crate::collateral::{
+    Collateral, CollateralBumps, __client_accounts_collateral, __cpi_client_accounts_collateral,
+};
+
+/// Buy outcome tokens along the bonding curve. The trading fee stays in the
+/// market vault and is split between creator, protocol and LPs
+pub(crate) fn buy_tokens(ctx: Context<BuyTokens>, outcome: u8, amount: u64, max_cost: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let now = Clock::get()?.unix_timestamp;
+    market.require_trading_open(now)?;
+    require!(
+        market.outcome_mint(outcome)? == ctx.accounts.outcome_mint.key(),
+        PredictionPumpError::InvalidOutcomeMint
+    );
+
+    let (cost, fee) = BondingCurve::calculate_buy_quote(
+        &market.curve_params_at(now),
+        ctx.accounts.outcome_mint.supply,
+        amount,
+    )?;
+    require!(cost <= max_cost, PredictionPumpError::SlippageExceeded);
+
+    // Collateral and fee go into the vault
+    ctx.accounts.collateral.deposit(
+        market,
+        &ctx.accounts.market_vault.to_account_info(),
+        &ctx.accounts.buyer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        cost,
+    )?;
+
+    // The vault PDA is the outcome mints' authority
+    let market_key = market.key();
+    let vault_seeds: &[&[u8]] = &[
+        b"market_vault",
+        market_key.as_ref(),
+        &[ctx.accounts.market_vault.bump],
+    ];
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.outcome_mint.to_account_info(),
+                to: ctx.accounts.buyer_token_account.to_account_info(),
+                authority: ctx.accounts.market_vault.to_account_info(),
+            },
+            &[vault_seeds],
+        ),
+        amount,
+    )?;
+
+    market.record_trade(cost, fee, now)?;
+
+    emit!(TradeEvent {
+        market: market_key,
+        trader: ctx.accounts.buyer.key(),
+        outcome,
+        is_buy: true,
+        token_amount: amount,
+        collateral_amount: cost,
+        fee,
+    });
+
+    Ok(())
+}
+
+/// Sell outcome tokens back to the bonding curve, net of the trading fee
+pub(crate) fn sell_tokens(ctx: Context<SellTokens>, outcome: u8, amount: u64, min_payout: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let now = Clock::get()?.unix_timestamp;
+    market.require_trading_open(now)?;
+    require!(
+        market.outcome_mint(outcome)? == ctx.accounts.outcome_mint.key(),
+        PredictionPumpError::InvalidOutcomeMint
+    );
+
+    let (payout, fee) = BondingCurve::calculate_sell_quote(
+        &market.curve_params_at(now),
+        ctx.accounts.outcome_mint.supply,
+        amount,
+    )?;
+    require!(payout >= min_payout, PredictionPumpError::SlippageExceeded);
+
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.outcome_mint.to_account_info(),
+                from: ctx.accounts.seller_token_account.to_account_info(),
+                authority: ctx.accounts.seller.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    // The fee never leaves the vault; reserve its creator and protocol shares
+    // before checking the remaining collateral covers the payout
+    market.record_trade(payout.checked_add(fee).ok_or(PredictionPumpError::MathOverflow)?, fee, now)?;
+
+    let vault_info = ctx.accounts.market_vault.to_account_info();
+    require!(
+        payout <= ctx.accounts.collateral.balance(market, &vault_info)?,
+        PredictionPumpError::InsufficientVaultFunds
+    );
+    ctx.accounts.collateral.withdraw(
+        market,
+        &vault_info,
+        ctx.accounts.market_vault.bump,
+        &ctx.accounts.seller.to_account_info(),
+        payout,
+    )?;
+
+    emit!(TradeEvent {
+        market: market.key(),
+        trader: ctx.accounts.seller.key(),
+        outcome,
+        is_buy: false,
+        token_amount: amount,
+        collateral_amount: payout,
+        fee,
+    });
+
+    Ok(())
+}
+
+/// Sweep the protocol's accrued share of trading fees to the treasury (anyone may call)
+pub(crate) fn claim_protocol_fees(ctx: Context<ClaimProtocolFees>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let amount = market.fees.claim_protocol()?;
+
+    ctx.accounts.collateral.withdraw(
+        market,
+        &ctx.accounts.market_vault.to_account_info(),
+        ctx.accounts.market_vault.bump,
+        &ctx.accounts.treasury.to_account_info(),
+        amount,
+    )?;
+
+    emit!(ProtocolFeesClaimedEvent {
+        market: market.key(),
+        treasury: ctx.accounts.treasury.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BuyTokens<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market_vault.bump
+    )]
+    pub market_vault: Account<'info, MarketVault>,
+
+    /// Mint of the outcome being bought, validated against the market
+    #[account(mut, mint::token_program = token_program)]
+    pub outcome_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = outcome_mint,
+        token::authority = buyer,
+        token::token_program = token_program
+    )]
+    pub buyer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// Collateral mint and token accounts; omitted for SOL markets
+    pub collateral: Collateral<'info>,
+
+    /// Token program owning the outcome mint, Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(BuyTokens::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SellTokens<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market_vault.bump
+    )]
+    pub market_vault: Account<'info, MarketVault>,
+
+    /// Mint of the outcome being sold, validated against the market
+    #[account(mut, mint::token_program = token_program)]
+    pub outcome_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = outcome_mint,
+        token::authority = seller,
+        token::token_program = token_program
+    )]
+    pub seller_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// Collateral mint and token accounts; omitted for SOL markets
+    pub collateral: Collateral<'info>,
+
+    /// Token program owning the outcome mint, Token or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Rejects the instruction while its pause bit is set
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        constraint = !protocol_config.is_paused(SellTokens::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimProtocolFees<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market_vault.bump
+    )]
+    pub market_vault: Account<'info, MarketVault>,
+
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump,
+        has_one = treasury @ PredictionPumpError::Unauthorized,
+        constraint = !protocol_config.is_paused(ClaimProtocolFees::PAUSE_FLAG, market.paused) @ PredictionPumpError::InstructionPaused
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(mut)]
+    /// CHECK: Must be the treasury recorded in the protocol config
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Collateral mint and token accounts; omitted for SOL markets
+    pub collateral: Collateral<'info>,
+}
+
+// Events for off-chain indexing and real-time updates
+
+#[event]
+pub struct TradeEvent {
+    pub market: Pubkey,
+    pub trader: Pubkey,
+    pub outcome: u8,
+    pub is_buy: bool,
+    pub token_amount: u64,
+    pub collateral_amount: u64, // paid in on a buy, paid out on a sell
+    pub fee: u64,
+}
+
+#[event]
+pub struct ProtocolFeesClaimedEvent {
+    pub market: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+}